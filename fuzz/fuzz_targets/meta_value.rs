@@ -0,0 +1,96 @@
+//! Feeds arbitrary token streams through the meta parsers.
+//!
+//! Run with `cargo fuzz run meta_value` from the repository root.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use macrotk_core::meta::{MetaList, MetaValue};
+
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::parse::Parser;
+
+fuzz_target!(|data: &[u8]| {
+    let input = match std::str::from_utf8(data) {
+        Ok(input) => input,
+        Err(_) => return,
+    };
+
+    // only inputs that lex are interesting, the parser never sees the rest
+    let tokens = match input.parse::<TokenStream>() {
+        Ok(tokens) => tokens,
+        Err(_) => return,
+    };
+
+    let lines = input.lines().count().max(1);
+
+    match syn::parse2::<MetaValue>(tokens.clone()) {
+        Ok(meta) => round_trip(&meta),
+        Err(err) => check_spans(&err, lines),
+    }
+
+    let eager = MetaList::parse_root_attr.parse2(tokens.clone());
+
+    if let Ok(list) = &eager {
+        let _ = list.to_string();
+    }
+
+    // forcing every lazy list has to agree with parsing eagerly
+    let eager = eager.and_then(|list| render_list(&list));
+    let lazy = MetaList::parse_root_attr_lazy
+        .parse2(tokens)
+        .and_then(|list| render_list(&list));
+
+    match (eager, lazy) {
+        (Ok(eager), Ok(lazy)) => assert_eq!(eager, lazy),
+        (Ok(eager), Err(err)) => panic!("`{}` only parses eagerly: {}", eager, err),
+        (Err(err), Ok(lazy)) => panic!("`{}` only parses lazily: {}", lazy, err),
+        (Err(eager), Err(lazy)) => {
+            check_spans(&eager, lines);
+            check_spans(&lazy, lines);
+        }
+    }
+});
+
+/// Printing a value and parsing it again gives the same value.
+fn round_trip(meta: &MetaValue) {
+    let printed = meta.to_token_stream();
+    let reparsed = syn::parse2::<MetaValue>(printed.clone())
+        .unwrap_or_else(|err| panic!("`{}` doesn't parse again: {}", printed, err));
+
+    assert_eq!(printed.to_string(), reparsed.to_token_stream().to_string());
+}
+
+/// Renders a value with every lazy list in it forced, so lazy and eager
+/// parses can be compared.
+fn render(meta: &MetaValue) -> syn::Result<String> {
+    match meta {
+        MetaValue::List(list) => render_list(list),
+        MetaValue::Lazy(lazy) => render_list(lazy.list()?),
+        meta => Ok(meta.to_token_stream().to_string()),
+    }
+}
+
+fn render_list(list: &MetaList) -> syn::Result<String> {
+    let entries = list.list.iter()
+        .map(render)
+        .collect::<syn::Result<Vec<_>>>()?;
+    let name = list.name.as_ref()
+        .map(|name| name.to_token_stream().to_string())
+        .unwrap_or_default();
+
+    Ok(format!("{}({})", name, entries.join(", ")))
+}
+
+/// Every error points somewhere in the input.
+fn check_spans(err: &syn::Error, lines: usize) {
+    for err in err.clone() {
+        let span: Span = err.span();
+        let (start, end) = (span.start(), span.end());
+
+        assert!(start <= end, "span ends before it starts: {:?}", span);
+        assert!(end.line <= lines, "span is past the end of the input: {:?}", span);
+    }
+}
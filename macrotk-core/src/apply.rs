@@ -0,0 +1,169 @@
+//! Rewriting items from parsed options.
+//!
+//! Options structs implement [`Apply`], usually by applying each of their
+//! fields in turn. The types in this module are ready-made fields for the
+//! common rewrites:
+//!
+//! ```ignore
+//! #[derive(FromMeta)]
+//! struct Opts {
+//!     #[macrotk(default)]
+//!     rename: Option<Rename>,
+//!     #[macrotk(default)]
+//!     derives: Option<Derives>,
+//! }
+//!
+//! impl Apply for Opts {
+//!     fn apply(&self, item: &mut DeriveInput) -> Result<(), Error> {
+//!         self.rename.apply(item)?;
+//!         self.derives.apply(item)
+//!     }
+//! }
+//! ```
+
+use syn::{Attribute, Data, DeriveInput, Error, Ident, Lit, Path};
+
+use quote::ToTokens;
+
+use proc_macro2::TokenStream;
+
+use crate::meta::{FromMeta, Meta, MetaValue};
+
+/// Types that can rewrite an item.
+pub trait Apply {
+    fn apply(&self, item: &mut DeriveInput) -> Result<(), Error>;
+}
+
+impl<T> Apply for Option<T>
+where T:
+    Apply,
+{
+    fn apply(&self, item: &mut DeriveInput) -> Result<(), Error> {
+        match self {
+            Some(t) => t.apply(item),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parses options from an attribute, applies them to the item and emits the
+/// rewritten item.
+///
+/// This is a whole attribute macro for the "parse options, tweak item,
+/// re-emit" kind of macro.
+pub fn apply<T>(attr: TokenStream, item: TokenStream) -> Result<TokenStream, Error>
+where T:
+    FromMeta + Apply,
+{
+    let opts = syn::parse2::<Meta<T>>(attr)?;
+    let mut item = syn::parse2::<DeriveInput>(item)?;
+
+    opts.apply(&mut item)?;
+
+    Ok(item.into_token_stream())
+}
+
+/// Renames the item, as in `rename = "NewName"`.
+#[derive(Clone)]
+pub struct Rename(pub Ident);
+
+impl FromMeta for Rename {
+    fn from_meta(meta: &MetaValue) -> Result<Rename, Error> {
+        match meta.literal()? {
+            Lit::Str(lit) => lit.parse().map(Rename),
+            lit => Err(Error::new(lit.span(), "expected str literal")),
+        }
+    }
+}
+
+impl Apply for Rename {
+    fn apply(&self, item: &mut DeriveInput) -> Result<(), Error> {
+        item.ident = self.0.clone();
+        Ok(())
+    }
+}
+
+/// Adds derives to the item, as in `derives(Clone, Debug)`.
+#[derive(Clone)]
+pub struct Derives(pub Vec<Path>);
+
+impl FromMeta for Derives {
+    fn from_meta(meta: &MetaValue) -> Result<Derives, Error> {
+        meta.list()?
+            .list
+            .iter()
+            .map(|meta| meta.path().cloned())
+            .collect::<Result<Vec<_>, Error>>()
+            .map(Derives)
+    }
+}
+
+impl Apply for Derives {
+    fn apply(&self, item: &mut DeriveInput) -> Result<(), Error> {
+        if !self.0.is_empty() {
+            let derives = &self.0;
+            item.attrs.push(syn::parse_quote!(#[derive(#(#derives),*)]));
+        }
+        Ok(())
+    }
+}
+
+/// Adds attributes to the item, as in `attrs(repr(C), doc = "...")`.
+#[derive(Clone)]
+pub struct ItemAttrs(pub Vec<Attribute>);
+
+impl FromMeta for ItemAttrs {
+    fn from_meta(meta: &MetaValue) -> Result<ItemAttrs, Error> {
+        attributes(meta).map(ItemAttrs)
+    }
+}
+
+impl Apply for ItemAttrs {
+    fn apply(&self, item: &mut DeriveInput) -> Result<(), Error> {
+        item.attrs.extend(self.0.iter().cloned());
+        Ok(())
+    }
+}
+
+/// Adds attributes to every field of the item, as in
+/// `field_attrs(serde(default))`.
+///
+/// For enums, this applies to the fields of every variant.
+#[derive(Clone)]
+pub struct FieldAttrs(pub Vec<Attribute>);
+
+impl FromMeta for FieldAttrs {
+    fn from_meta(meta: &MetaValue) -> Result<FieldAttrs, Error> {
+        attributes(meta).map(FieldAttrs)
+    }
+}
+
+impl Apply for FieldAttrs {
+    fn apply(&self, item: &mut DeriveInput) -> Result<(), Error> {
+        let fields: Vec<&mut syn::Field> = match &mut item.data {
+            Data::Struct(s) => s.fields.iter_mut().collect(),
+            Data::Enum(e) => e.variants
+                .iter_mut()
+                .flat_map(|v| v.fields.iter_mut())
+                .collect(),
+            Data::Union(u) => u.fields.named.iter_mut().collect(),
+        };
+
+        for field in fields {
+            field.attrs.extend(self.0.iter().cloned());
+        }
+
+        Ok(())
+    }
+}
+
+fn attributes(meta: &MetaValue) -> Result<Vec<Attribute>, Error> {
+    meta.list()?
+        .list
+        .iter()
+        .map(|meta| match meta {
+            MetaValue::Lit(lit) => Err(Error::new(lit.span(), "expected an attribute")),
+            meta => Ok(syn::parse_quote!(#[#meta])),
+        })
+        .collect()
+}
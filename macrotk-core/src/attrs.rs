@@ -0,0 +1,161 @@
+//! Helpers for passing attributes through item rewrites.
+
+use syn::Attribute;
+
+/// What to do with an attribute when an item is rewritten into an original
+/// item and a generated item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttrPolicy {
+    /// Keep the attribute on the original item.
+    Keep,
+    /// Remove the attribute entirely.
+    Strip,
+    /// Move the attribute to the generated item.
+    Move,
+    /// Put the attribute on both items.
+    Copy,
+}
+
+impl AttrPolicy {
+    /// The policy most attribute macros that wrap items want.
+    ///
+    /// * `cfg` and lint attributes (`allow`, `warn`, `deny`, `forbid`) are
+    ///   copied to both items.
+    /// * `doc` attributes are moved to the generated item, since that is
+    ///   usually the one users see.
+    /// * Everything else is kept on the original item.
+    pub fn default_for(attr: &Attribute) -> AttrPolicy {
+        if is_cfg(attr) || is_lint(attr) {
+            AttrPolicy::Copy
+        } else if is_named(attr, "doc") {
+            AttrPolicy::Move
+        } else {
+            AttrPolicy::Keep
+        }
+    }
+}
+
+/// Attributes partitioned by [`partition_attrs`].
+#[derive(Clone, Default)]
+pub struct PartitionedAttrs {
+    /// Attributes for the original item.
+    pub original: Vec<Attribute>,
+    /// Attributes for the generated item.
+    pub generated: Vec<Attribute>,
+}
+
+/// Partitions attributes by a policy, preserving their order.
+///
+/// `cfg` attributes are always copied to both items unless they are
+/// stripped, because an item that exists without its counterpart will not
+/// compile.
+pub fn partition_attrs<I, F>(attrs: I, mut policy: F) -> PartitionedAttrs
+where
+    I: IntoIterator<Item = Attribute>,
+    F: FnMut(&Attribute) -> AttrPolicy,
+{
+    let mut partitioned = PartitionedAttrs::default();
+
+    for attr in attrs {
+        let policy = match policy(&attr) {
+            AttrPolicy::Strip => AttrPolicy::Strip,
+            _ if is_cfg(&attr) => AttrPolicy::Copy,
+            policy => policy,
+        };
+
+        match policy {
+            AttrPolicy::Keep => partitioned.original.push(attr),
+            AttrPolicy::Strip => (),
+            AttrPolicy::Move => partitioned.generated.push(attr),
+            AttrPolicy::Copy => {
+                partitioned.generated.push(attr.clone());
+                partitioned.original.push(attr);
+            }
+        }
+    }
+
+    partitioned
+}
+
+fn is_named(attr: &Attribute, name: &str) -> bool {
+    attr.path.get_ident().map(|i| i == name).unwrap_or(false)
+}
+
+fn is_cfg(attr: &Attribute) -> bool {
+    is_named(attr, "cfg")
+}
+
+fn is_lint(attr: &Attribute) -> bool {
+    ["allow", "warn", "deny", "forbid"].iter().any(|name| is_named(attr, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use syn::parse_quote;
+
+    use quote::quote;
+
+    fn render(attrs: &[Attribute]) -> String {
+        quote!(#(#attrs)*).to_string()
+    }
+
+    #[test]
+    pub fn named_attrs() {
+        let doc: Attribute = parse_quote!(#[doc = "hi"]);
+        let lint: Attribute = parse_quote!(#[deny(missing_docs)]);
+        let path: Attribute = parse_quote!(#[serde::doc]);
+
+        assert!(is_named(&doc, "doc"));
+        assert!(!is_named(&doc, "cfg"));
+        assert!(is_lint(&lint));
+        // only single-segment paths match
+        assert!(!is_named(&path, "doc"));
+    }
+
+    #[test]
+    pub fn partition_default() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[doc = "one"]),
+            parse_quote!(#[inline]),
+            parse_quote!(#[cfg(test)]),
+            parse_quote!(#[allow(dead_code)]),
+            parse_quote!(#[doc = "two"]),
+        ];
+
+        let partitioned = partition_attrs(attrs, AttrPolicy::default_for);
+
+        assert_eq!(
+            render(&partitioned.original),
+            quote!(#[inline] #[cfg(test)] #[allow(dead_code)]).to_string(),
+        );
+        assert_eq!(
+            render(&partitioned.generated),
+            quote!(
+                #[doc = "one"] #[cfg(test)] #[allow(dead_code)] #[doc = "two"]
+            ).to_string(),
+        );
+    }
+
+    #[test]
+    pub fn partition_cfg() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[cfg(a)]),
+            parse_quote!(#[cfg(b)]),
+            parse_quote!(#[inline]),
+        ];
+
+        // cfg can't be moved or kept on one side, only stripped
+        let partitioned = partition_attrs(attrs, |attr| {
+            if attr.path.is_ident("inline") || attr.tokens.to_string().contains('b') {
+                AttrPolicy::Strip
+            } else {
+                AttrPolicy::Move
+            }
+        });
+
+        assert_eq!(render(&partitioned.original), quote!(#[cfg(a)]).to_string());
+        assert_eq!(render(&partitioned.generated), quote!(#[cfg(a)]).to_string());
+    }
+}
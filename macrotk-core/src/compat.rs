@@ -0,0 +1,139 @@
+//! Conversions from syn's meta types.
+//!
+//! These let attribute macros that already parse their arguments as
+//! [`AttributeArgs`] use [`FromMeta`] types without changing their entry
+//! points:
+//!
+//! ```ignore
+//! let args = parse_macro_input!(attr as AttributeArgs);
+//! let opts = match macrotk::compat::from_attribute_args::<MacroMeta>(args) {
+//!     Ok(opts) => opts,
+//!     Err(err) => return err.into_compile_error().into(),
+//! };
+//! ```
+
+use syn::{AttributeArgs, Error, Meta, NestedMeta};
+
+use crate::meta::{FromMeta, MetaList, MetaNameValue, MetaValue};
+
+/// Parses a [`FromMeta`] type from attribute arguments.
+pub fn from_attribute_args<T>(args: AttributeArgs) -> Result<T, Error>
+where T:
+    FromMeta,
+{
+    T::from_meta(&MetaList::from(args).into())
+}
+
+impl From<NestedMeta> for MetaValue {
+    fn from(nested: NestedMeta) -> MetaValue {
+        match nested {
+            NestedMeta::Lit(lit) => MetaValue::Lit(lit),
+            NestedMeta::Meta(meta) => meta.into(),
+        }
+    }
+}
+
+impl From<Meta> for MetaValue {
+    fn from(meta: Meta) -> MetaValue {
+        match meta {
+            Meta::Path(path) => MetaValue::Path(path),
+            Meta::List(list) => MetaValue::List(list.into()),
+            Meta::NameValue(nv) => MetaValue::NameValue(MetaNameValue {
+                name: nv.path,
+                eq: nv.eq_token,
+                value: Box::new(MetaValue::Lit(nv.lit)),
+            }),
+        }
+    }
+}
+
+impl From<syn::MetaList> for MetaList {
+    fn from(list: syn::MetaList) -> MetaList {
+        MetaList {
+            name: Some(list.path),
+            paren: Some(list.paren_token),
+            list: list.nested.into_iter().map(MetaValue::from).collect(),
+        }
+    }
+}
+
+/// Converts attribute arguments into a root list.
+impl From<Vec<NestedMeta>> for MetaList {
+    fn from(args: Vec<NestedMeta>) -> MetaList {
+        args.into_iter().collect()
+    }
+}
+
+/// Converts attribute arguments into a root list.
+impl From<Vec<NestedMeta>> for MetaValue {
+    fn from(args: Vec<NestedMeta>) -> MetaValue {
+        MetaValue::List(args.into())
+    }
+}
+
+impl std::iter::FromIterator<NestedMeta> for MetaList {
+    fn from_iter<I>(iter: I) -> MetaList
+    where I:
+        IntoIterator<Item = NestedMeta>,
+    {
+        MetaList {
+            name: None,
+            paren: None,
+            list: iter.into_iter().map(MetaValue::from).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use syn::parse::Parser;
+    use syn::punctuated::Punctuated;
+
+    use quote::ToTokens;
+
+    fn attribute_args(attr: &str) -> AttributeArgs {
+        Punctuated::<NestedMeta, syn::Token![,]>::parse_terminated
+            .parse_str(attr)
+            .unwrap()
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    pub fn round_trip() {
+        let attr = r#"name = "x", verbose, retry(count = 3, backoff(max = 10)), "lone", 4"#;
+        let list = MetaList::from(attribute_args(attr));
+
+        let parsed = MetaList::parse_root_attr.parse_str(attr).unwrap();
+        assert_eq!(
+            list.to_token_stream().to_string(),
+            parsed.to_token_stream().to_string(),
+        );
+
+        let retry = list.find("retry").unwrap().list().unwrap();
+        assert_eq!(retry.get::<u32>("count").unwrap().unwrap(), 3);
+        let backoff = retry.find("backoff").unwrap().list().unwrap();
+        assert_eq!(backoff.get::<u32>("max").unwrap().unwrap(), 10);
+
+        assert_eq!(list.get_index::<syn::LitStr>(3).unwrap().unwrap().value(), "lone");
+        assert_eq!(list.last::<u32>().unwrap().unwrap(), 4);
+    }
+
+    #[test]
+    pub fn literal_only() {
+        let list = MetaList::from(attribute_args(r#""a", 1, true"#));
+
+        assert_eq!(list.list.len(), 3);
+        assert!(list.list.iter().all(|entry| matches!(entry, MetaValue::Lit(_))));
+        assert_eq!(list.first::<syn::LitStr>().unwrap().unwrap().value(), "a");
+    }
+
+    #[test]
+    pub fn from_args() {
+        let names = from_attribute_args::<Vec<syn::LitStr>>(attribute_args(r#""a", "b""#)).unwrap();
+
+        assert_eq!(names.iter().map(syn::LitStr::value).collect::<Vec<_>>(), ["a", "b"]);
+    }
+}
@@ -0,0 +1,110 @@
+//! Baseline options read from a TOML file in the crate being expanded.
+//!
+//! A file like
+//!
+//! ```toml
+//! threads = 4
+//!
+//! [retry]
+//! count = 3
+//! ```
+//!
+//! reads as `threads = 4, retry(count = 3)`.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use syn::{Error, Lit, LitBool, LitFloat, LitInt, LitStr};
+
+use proc_macro2::Span;
+
+use ::toml::{Table, Value};
+
+use crate::meta::{MetaList, MetaValue};
+
+/// Reads `file_name` in the manifest dir of the crate being expanded.
+///
+/// Returns `None` if there is no such file.
+pub fn load(file_name: &str) -> Result<Option<MetaList>, Error> {
+    let dir = match std::env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => dir,
+        None => return Ok(None),
+    };
+    let path = Path::new(&dir).join(file_name);
+
+    match fs::read_to_string(&path) {
+        Ok(text) => from_str(&path, &text).map(Some),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(file_error(&path, err)),
+    }
+}
+
+/// Parses the contents of the file at `path`.
+pub fn from_str(path: &Path, text: &str) -> Result<MetaList, Error> {
+    let table = text.parse::<Table>().map_err(|err| file_error(path, err))?;
+
+    from_table(path, table)
+}
+
+fn from_table(path: &Path, table: Table) -> Result<MetaList, Error> {
+    let list = table.into_iter()
+        .map(|(key, value)| Ok(MetaValue::entry(&key, from_value(path, &key, value)?)))
+        .collect::<Result<_, Error>>()?;
+
+    Ok(MetaList { name: None, paren: None, list })
+}
+
+fn from_value(path: &Path, key: &str, value: Value) -> Result<MetaValue, Error> {
+    let span = Span::call_site();
+
+    let lit = match value {
+        Value::String(s) => Lit::Str(LitStr::new(&s, span)),
+        Value::Integer(i) => Lit::Int(LitInt::new(&i.to_string(), span)),
+        Value::Float(f) if f.is_finite() => Lit::Float(LitFloat::new(&format!("{:?}", f), span)),
+        Value::Boolean(b) => Lit::Bool(LitBool { value: b, span }),
+        Value::Table(table) => return from_table(path, table).map(MetaValue::List),
+        Value::Array(values) => {
+            let list = values.into_iter()
+                .map(|value| from_value(path, key, value))
+                .collect::<Result<_, Error>>()?;
+
+            return Ok(MetaValue::List(MetaList { name: None, paren: None, list }));
+        }
+        value => {
+            return Err(file_error(path, format!("unsupported value for `{}`: {}", key, value)))
+        }
+    };
+
+    Ok(MetaValue::Lit(lit))
+}
+
+fn file_error<T>(path: &Path, err: T) -> Error
+where T:
+    std::fmt::Display,
+{
+    Error::new(Span::call_site(), format!("{}: {}", path.display(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn read_config() {
+        let list = from_str(
+            Path::new("macrotk.toml"),
+            "name = \"x\"\nthreads = 4\n[retry]\ncount = 3\n",
+        )
+        .unwrap();
+
+        assert_eq!(list.to_string(), "name = \"x\", retry(count = 3), threads = 4");
+    }
+
+    #[test]
+    pub fn config_error_has_path() {
+        let err = from_str(Path::new("macrotk.toml"), "threads = ").err().unwrap();
+
+        assert!(err.to_string().starts_with("macrotk.toml: "));
+    }
+}
@@ -0,0 +1,120 @@
+//! Interoperability with [`darling`](::darling).
+//!
+//! This goes both ways: [`MetaValue`] and [`MetaList`] can be used as fields
+//! of darling's `FromMeta` types, and [`Darling`] lets any of darling's
+//! `FromMeta` types be used as a field of macrotk's.
+
+use ::darling::FromMeta as DarlingFromMeta;
+
+use syn::{Error, Meta, MetaList as SynMetaList, MetaNameValue as SynMetaNameValue, NestedMeta};
+
+use crate::meta::{FromMeta, MetaList, MetaValue};
+
+/// Converts a meta value into darling's representation.
+///
+/// Root lists have no name, so they can't be converted. Use
+/// [`to_nested_list`] for their entries instead. Name-value pairs can only
+/// be converted if their value is a literal, and lifetimes and string keys
+/// can't be converted at all.
+pub fn to_nested_meta(meta: &MetaValue) -> Option<NestedMeta> {
+    let nested = match meta {
+        MetaValue::Lit(lit) => NestedMeta::Lit(lit.clone()),
+        MetaValue::Path(path) => NestedMeta::Meta(Meta::Path(path.clone())),
+        MetaValue::NameValue(nv) => NestedMeta::Meta(Meta::NameValue(SynMetaNameValue {
+            path: nv.name.clone(),
+            eq_token: nv.eq,
+            lit: nv.value.literal().ok()?.clone(),
+        })),
+        MetaValue::List(list) => list_to_nested_meta(list)?,
+        MetaValue::Lazy(lazy) => list_to_nested_meta(lazy.list().ok()?)?,
+        // darling has no lifetime values or string keys
+        MetaValue::Lifetime(_) | MetaValue::StrNameValue(_) => return None,
+    };
+
+    Some(nested)
+}
+
+fn list_to_nested_meta(list: &MetaList) -> Option<NestedMeta> {
+    Some(NestedMeta::Meta(Meta::List(SynMetaList {
+        path: list.name.clone()?,
+        paren_token: list.paren.unwrap_or_default(),
+        nested: to_nested_list(list).into_iter().collect(),
+    })))
+}
+
+/// Converts the entries of a list into darling's representation.
+///
+/// The entries of nested root lists are spliced in.
+pub fn to_nested_list(list: &MetaList) -> Vec<NestedMeta> {
+    list.list.iter()
+        .flat_map(|meta| match (to_nested_meta(meta), meta) {
+            (Some(nested), _) => vec![nested],
+            (None, MetaValue::List(list)) => to_nested_list(list),
+            (None, _) => Vec::new(),
+        })
+        .collect()
+}
+
+impl DarlingFromMeta for MetaValue {
+    fn from_meta(item: &Meta) -> ::darling::Result<MetaValue> {
+        Ok(item.clone().into())
+    }
+
+    fn from_value(value: &syn::Lit) -> ::darling::Result<MetaValue> {
+        Ok(MetaValue::Lit(value.clone()))
+    }
+}
+
+impl DarlingFromMeta for MetaList {
+    fn from_meta(item: &Meta) -> ::darling::Result<MetaList> {
+        match item {
+            Meta::List(list) => Ok(list.clone().into()),
+            _ => Err(::darling::Error::unexpected_type("non-list").with_span(item)),
+        }
+    }
+}
+
+/// Adapts a darling [`FromMeta`](::darling::FromMeta) type into a macrotk
+/// [`FromMeta`] type.
+#[derive(Clone, Debug, Default)]
+pub struct Darling<T>(pub T);
+
+impl<T> FromMeta for Darling<T>
+where T:
+    DarlingFromMeta,
+{
+    fn from_meta(meta: &MetaValue) -> Result<Darling<T>, Error> {
+        let result = match to_nested_meta(meta) {
+            Some(nested) => T::from_nested_meta(&nested),
+            None => T::from_list(&to_nested_list(meta.list()?)),
+        };
+
+        result
+            .map(Darling)
+            .map_err(|err| err.with_span(meta).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use syn::parse::Parser;
+
+    #[derive(::darling::FromMeta)]
+    struct Retry {
+        count: u32,
+        backoff: ::darling::util::Flag,
+    }
+
+    #[test]
+    pub fn darling_field() {
+        let meta = MetaList::parse_root_attr
+            .parse_str("retry(count = 3, backoff)")
+            .unwrap();
+        let retry = meta.get::<Darling<Retry>>("retry").unwrap().unwrap().0;
+
+        assert_eq!(retry.count, 3);
+        assert!(retry.backoff.is_present());
+    }
+}
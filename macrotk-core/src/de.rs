@@ -0,0 +1,606 @@
+//! A [`serde`] deserializer for meta values.
+//!
+//! Meta maps onto the serde data model like so:
+//!
+//! * Literals deserialize as their value.
+//! * Lists of named entries (`a = 1, b(..), c`) deserialize as maps or
+//!   structs. A bare path in a list, like `c`, is a flag that deserializes
+//!   as `true`, and the value of `b(..)` is the contents of the list.
+//! * Any other list deserializes as a sequence.
+//! * Paths (outside of maps) and string literals deserialize as identifiers,
+//!   so they can select unit variants.
+//! * Named lists (`json(pretty = true)`) deserialize as enum variants with
+//!   content, so `codec(json(pretty = true))` sets `codec` to a variant.
+
+use std::fmt;
+
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess,
+    SeqAccess, VariantAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+
+use syn::spanned::Spanned;
+use syn::{Lit, Path};
+
+use proc_macro2::Span;
+
+use crate::meta::{FromMeta, MetaList, MetaNameValue, MetaStrNameValue, MetaValue};
+
+/// Deserializes a type from a meta value.
+pub fn from_meta<T>(meta: &MetaValue) -> Result<T, syn::Error>
+where T:
+    DeserializeOwned,
+{
+    T::deserialize(Deserializer::new(meta)).map_err(Into::into)
+}
+
+/// Adapts a [`Deserialize`](serde::Deserialize) type into a [`FromMeta`]
+/// type.
+#[derive(Clone, Debug, Default)]
+pub struct Serde<T>(pub T);
+
+impl<T> FromMeta for Serde<T>
+where T:
+    DeserializeOwned,
+{
+    fn from_meta(meta: &MetaValue) -> Result<Serde<T>, syn::Error> {
+        from_meta(meta).map(Serde)
+    }
+}
+
+/// An error produced while deserializing.
+///
+/// Errors raised by [`Deserialize`](serde::Deserialize) impls carry no
+/// location, so they are spanned on the innermost value being deserialized
+/// when they occurred.
+#[derive(Debug)]
+pub struct DeError {
+    span: Option<Span>,
+    msg: String,
+}
+
+impl DeError {
+    fn spanned(mut self, span: Span) -> DeError {
+        self.span.get_or_insert(span);
+        self
+    }
+}
+
+impl fmt::Display for DeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.msg)
+    }
+}
+
+impl std::error::Error for DeError {}
+
+impl de::Error for DeError {
+    fn custom<T>(msg: T) -> DeError
+    where T:
+        fmt::Display,
+    {
+        DeError {
+            span: None,
+            msg: msg.to_string(),
+        }
+    }
+}
+
+impl From<DeError> for syn::Error {
+    fn from(err: DeError) -> syn::Error {
+        syn::Error::new(err.span.unwrap_or_else(Span::call_site), err.msg)
+    }
+}
+
+/// A deserializer over a [`MetaValue`].
+#[derive(Clone, Copy)]
+pub struct Deserializer<'a> {
+    node: Node<'a>,
+}
+
+#[derive(Clone, Copy)]
+enum Node<'a> {
+    Path(&'a Path),
+    NameValue(&'a MetaNameValue),
+    StrNameValue(&'a MetaStrNameValue),
+    List(&'a MetaList),
+    /// A list whose entries are the value, like a map value `key(...)` or a
+    /// root list.
+    Contents(&'a MetaList),
+    Lit(&'a Lit),
+    /// A bare path used as a map entry.
+    Flag(&'a Path),
+    /// A lazy list that failed to parse.
+    Invalid(&'a syn::Error),
+    Lifetime(&'a syn::Lifetime),
+}
+
+impl<'a> Deserializer<'a> {
+    pub fn new(meta: &'a MetaValue) -> Deserializer<'a> {
+        Deserializer {
+            node: match meta {
+                MetaValue::Path(path) => Node::Path(path),
+                MetaValue::NameValue(nv) => Node::NameValue(nv),
+                MetaValue::StrNameValue(nv) => Node::StrNameValue(nv),
+                MetaValue::List(list) if list.name.is_none() => Node::Contents(list),
+                MetaValue::List(list) => Node::List(list),
+                MetaValue::Lit(lit) => Node::Lit(lit),
+                MetaValue::Lifetime(lt) => Node::Lifetime(lt),
+                MetaValue::Lazy(lazy) => match lazy.parsed() {
+                    Ok(list) => Node::List(list),
+                    Err(err) => Node::Invalid(err),
+                },
+            },
+        }
+    }
+
+    /// Creates a deserializer over the entries of a list.
+    pub fn from_list(list: &'a MetaList) -> Deserializer<'a> {
+        Deserializer {
+            node: Node::Contents(list),
+        }
+    }
+
+    /// The only entry of a list, if it is unnamed.
+    fn single(list: &'a MetaList) -> Option<Deserializer<'a>> {
+        match list.list.first() {
+            Some(meta) if list.list.len() == 1 && meta.key().is_none() => {
+                Some(Deserializer::new(meta))
+            }
+            _ => None,
+        }
+    }
+
+    fn span(&self) -> Span {
+        match self.node {
+            Node::Path(path) | Node::Flag(path) => path.span(),
+            Node::NameValue(nv) => nv.span(),
+            Node::StrNameValue(nv) => nv.span(),
+            Node::List(list) | Node::Contents(list) => list.span(),
+            Node::Lit(lit) => lit.span(),
+            Node::Lifetime(lt) => lt.span(),
+            Node::Invalid(err) => err.span(),
+        }
+    }
+
+    fn invalid_type(&self, exp: &dyn de::Expected) -> DeError {
+        let unexpected = match self.node {
+            Node::Path(_) => de::Unexpected::Other("path"),
+            Node::NameValue(_) | Node::StrNameValue(_) => de::Unexpected::Other("name-value pair"),
+            Node::List(_) | Node::Contents(_) => de::Unexpected::Other("list"),
+            Node::Lit(_) => de::Unexpected::Other("literal"),
+            Node::Lifetime(_) => de::Unexpected::Other("lifetime"),
+            Node::Flag(_) => de::Unexpected::Bool(true),
+            // the parse error is more useful than anything we could say
+            Node::Invalid(err) => return DeError {
+                span: Some(err.span()),
+                msg: err.to_string(),
+            },
+        };
+
+        de::Error::invalid_type(unexpected, exp)
+    }
+
+    fn visit_lit<'de, V>(lit: &Lit, visitor: V) -> Result<V::Value, DeError>
+    where V:
+        Visitor<'de>,
+    {
+        match lit {
+            Lit::Str(s) => visitor.visit_string(s.value()),
+            Lit::ByteStr(s) => visitor.visit_byte_buf(s.value()),
+            Lit::Byte(b) => visitor.visit_u8(b.value()),
+            Lit::Char(c) => visitor.visit_char(c.value()),
+            // negative literals keep their sign in their digits
+            Lit::Int(i) if i.base10_digits().starts_with('-') => match i.base10_parse::<i64>() {
+                Ok(i) => visitor.visit_i64(i),
+                Err(err) => Err(de::Error::custom(err)),
+            },
+            Lit::Int(i) => match i.base10_parse::<u64>() {
+                Ok(i) => visitor.visit_u64(i),
+                Err(err) => Err(de::Error::custom(err)),
+            },
+            Lit::Float(f) => match f.base10_parse::<f64>() {
+                Ok(f) => visitor.visit_f64(f),
+                Err(err) => Err(de::Error::custom(err)),
+            },
+            Lit::Bool(b) => visitor.visit_bool(b.value),
+            Lit::Verbatim(_) => Err(de::Error::custom("unsupported literal")),
+        }
+    }
+}
+
+fn path_name(path: &Path) -> String {
+    path.segments.last()
+        .map(|s| s.ident.to_string())
+        .unwrap_or_default()
+}
+
+fn is_named(list: &MetaList) -> bool {
+    list.list.iter().all(|meta| meta.key().is_some())
+}
+
+impl<'de, 'a> de::Deserializer<'de> for Deserializer<'a> {
+    type Error = DeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where V:
+        Visitor<'de>,
+    {
+        let span = self.span();
+
+        if let Node::Contents(list) = self.node {
+            // `key(5)` is the same as `key = 5`
+            if let Some(single) = Self::single(list) {
+                return single.deserialize_any(visitor);
+            }
+        }
+
+        let result = match self.node {
+            Node::Lit(lit) => Self::visit_lit(lit, visitor),
+            Node::Flag(_) => visitor.visit_bool(true),
+            Node::Path(path) => visitor.visit_string(path_name(path)),
+            Node::Lifetime(lt) => visitor.visit_string(lt.to_string()),
+            Node::List(list) | Node::Contents(list) if is_named(list) => {
+                visitor.visit_map(ListAccess::new(list))
+            }
+            Node::List(list) | Node::Contents(list) => visitor.visit_seq(ListAccess::new(list)),
+            Node::NameValue(_) | Node::StrNameValue(_) | Node::Invalid(_) => {
+                Err(self.invalid_type(&visitor))
+            }
+        };
+
+        result.map_err(|err| err.spanned(span))
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where V:
+        Visitor<'de>,
+    {
+        // `key = none`, like `Option`'s `FromMeta`
+        match self.node {
+            Node::Path(path) if path.is_ident("none") => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    where V:
+        Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where V:
+        Visitor<'de>,
+    {
+        match self.node {
+            Node::List(list) | Node::Contents(list) => visitor
+                .visit_seq(ListAccess::new(list))
+                .map_err(|err| err.spanned(self.span())),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, DeError>
+    where V:
+        Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    where V:
+        Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where V:
+        Visitor<'de>,
+    {
+        match self.node {
+            Node::List(list) | Node::Contents(list) => visitor
+                .visit_map(ListAccess::new(list))
+                .map_err(|err| err.spanned(self.span())),
+            _ => Err(self.invalid_type(&visitor).spanned(self.span())),
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    where V:
+        Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    where V:
+        Visitor<'de>,
+    {
+        let span = self.span();
+
+        if let Node::Contents(list) = self.node {
+            // the entry of the list is the variant
+            return match list.list.first() {
+                Some(meta) if list.list.len() == 1 => {
+                    Deserializer::new(meta).deserialize_enum(name, variants, visitor)
+                }
+                _ => Err(<DeError as de::Error>::custom("expected exactly one variant")
+                    .spanned(span)),
+            };
+        }
+
+        let (variant, content) = match self.node {
+            Node::Path(path) => (path_name(path), None),
+            Node::Lit(Lit::Str(s)) => (s.value(), None),
+            Node::List(list) => match &list.name {
+                Some(name) => (path_name(name), Some(list)),
+                None => return Err(self.invalid_type(&visitor).spanned(span)),
+            },
+            _ => return Err(self.invalid_type(&visitor).spanned(span)),
+        };
+
+        visitor
+            .visit_enum(Enum { variant, content })
+            .map_err(|err| err.spanned(span))
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where V:
+        Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, DeError>
+    where V:
+        Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct
+    }
+}
+
+struct ListAccess<'a> {
+    iter: syn::punctuated::Iter<'a, MetaValue>,
+    value: Option<Deserializer<'a>>,
+}
+
+impl<'a> ListAccess<'a> {
+    fn new(list: &'a MetaList) -> ListAccess<'a> {
+        ListAccess {
+            iter: list.list.iter(),
+            value: None,
+        }
+    }
+}
+
+impl<'de, 'a> SeqAccess<'de> for ListAccess<'a> {
+    type Error = DeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, DeError>
+    where T:
+        DeserializeSeed<'de>,
+    {
+        self.iter.next()
+            .map(|meta| seed.deserialize(Deserializer::new(meta)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+impl<'de, 'a> MapAccess<'de> for ListAccess<'a> {
+    type Error = DeError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, DeError>
+    where K:
+        DeserializeSeed<'de>,
+    {
+        let meta = match self.iter.next() {
+            Some(meta) => meta,
+            None => return Ok(None),
+        };
+
+        let (name, span) = meta.key()
+            .zip(meta.key_span())
+            .ok_or_else(|| {
+                <DeError as de::Error>::custom("expected a named entry").spanned(meta.span())
+            })?;
+
+        self.value = Some(Deserializer {
+            node: match meta {
+                MetaValue::NameValue(nv) => Deserializer::new(&nv.value).node,
+                MetaValue::StrNameValue(nv) => Deserializer::new(&nv.value).node,
+                MetaValue::Path(path) => Node::Flag(path),
+                MetaValue::List(list) => Node::Contents(list),
+                MetaValue::Lit(lit) => Node::Lit(lit),
+                MetaValue::Lifetime(lt) => Node::Lifetime(lt),
+                MetaValue::Lazy(lazy) => match lazy.parsed() {
+                    Ok(list) => Node::Contents(list),
+                    Err(err) => Node::Invalid(err),
+                },
+            },
+        });
+
+        seed.deserialize(name.into_deserializer())
+            .map(Some)
+            .map_err(|err: DeError| err.spanned(span))
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, DeError>
+    where V:
+        DeserializeSeed<'de>,
+    {
+        let value = self.value.take()
+            .ok_or_else(|| <DeError as de::Error>::custom("value is missing"))?;
+
+        seed.deserialize(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct Enum<'a> {
+    variant: String,
+    content: Option<&'a MetaList>,
+}
+
+impl<'de, 'a> EnumAccess<'de> for Enum<'a> {
+    type Error = DeError;
+    type Variant = Variant<'a>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Variant<'a>), DeError>
+    where V:
+        DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(self.variant.into_deserializer())?;
+
+        Ok((variant, Variant { content: self.content }))
+    }
+}
+
+struct Variant<'a> {
+    content: Option<&'a MetaList>,
+}
+
+impl<'a> Variant<'a> {
+    fn content(&self) -> Result<&'a MetaList, DeError> {
+        self.content.ok_or_else(|| <DeError as de::Error>::custom("expected a list after the variant name"))
+    }
+}
+
+impl<'de, 'a> VariantAccess<'de> for Variant<'a> {
+    type Error = DeError;
+
+    fn unit_variant(self) -> Result<(), DeError> {
+        match self.content {
+            None => Ok(()),
+            Some(list) => Err(<DeError as de::Error>::custom("unexpected list; expected a unit variant")
+                .spanned(list.span())),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, DeError>
+    where T:
+        DeserializeSeed<'de>,
+    {
+        seed.deserialize(Deserializer::from_list(self.content()?))
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, DeError>
+    where V:
+        Visitor<'de>,
+    {
+        visitor.visit_seq(ListAccess::new(self.content()?))
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError>
+    where V:
+        Visitor<'de>,
+    {
+        visitor.visit_map(ListAccess::new(self.content()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde::Deserialize;
+
+    use crate::meta::Meta;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Codec {
+        Json { pretty: bool },
+        Raw,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Opts {
+        name: String,
+        retries: u32,
+        verbose: bool,
+        tags: Vec<String>,
+        codec: Codec,
+        #[serde(default)]
+        fallback: Option<Codec>,
+    }
+
+    #[test]
+    pub fn deserialize_struct() {
+        let meta: Meta<Serde<Opts>> = syn::parse_str(
+            r#"name = "x", retries = 3, verbose, tags("a", b), codec(json(pretty = false)), fallback = none"#,
+        ).unwrap();
+
+        assert_eq!(meta.into_inner().0, Opts {
+            name: "x".into(),
+            retries: 3,
+            verbose: true,
+            tags: vec!["a".into(), "b".into()],
+            codec: Codec::Json { pretty: false },
+            fallback: None,
+        });
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Offset {
+        x: i32,
+        y: i64,
+    }
+
+    #[test]
+    pub fn deserialize_negative() {
+        let meta: Meta<Serde<Offset>> = syn::parse_str("x = -1, y = 2").unwrap();
+
+        assert_eq!(meta.into_inner().0, Offset { x: -1, y: 2 });
+    }
+
+    #[test]
+    pub fn deserialize_error_span() {
+        let err = syn::parse_str::<Meta<Serde<Opts>>>("name = 1")
+            .err()
+            .unwrap();
+
+        assert!(err.to_string().contains("invalid type"));
+
+        // the error points at `1`, not the whole attribute
+        assert_eq!((err.span().start().column, err.span().end().column), (7, 8));
+    }
+}
@@ -0,0 +1,36 @@
+//! Helpers for inspecting generated code during development.
+
+use proc_macro2::TokenStream;
+
+/// Pretty-prints a token stream as Rust source.
+///
+/// If the tokens don't form a sequence of items (an expression, for example),
+/// this falls back to the plain [`TokenStream`] rendering.
+pub fn pretty(tokens: &TokenStream) -> String {
+    match syn::parse2::<syn::File>(tokens.clone()) {
+        Ok(file) => prettyplease::unparse(&file),
+        Err(_) => tokens.to_string(),
+    }
+}
+
+/// Prints a pretty-printed token stream to stderr, returning it.
+///
+/// This works like [`std::dbg!`], but for anything that implements
+/// [`ToTokens`](quote::ToTokens).
+#[macro_export]
+macro_rules! dbg_tokens {
+    ($val:expr $(,)?) => {
+        match $val {
+            tmp => {
+                ::std::eprintln!(
+                    "[{}:{}] {} =\n{}",
+                    ::std::file!(),
+                    ::std::line!(),
+                    ::std::stringify!($val),
+                    $crate::debug::pretty(&$crate::quote::ToTokens::to_token_stream(&tmp)),
+                );
+                tmp
+            }
+        }
+    };
+}
@@ -0,0 +1,55 @@
+//! Doc comment helpers.
+
+use syn::{Attribute, Lit, Meta};
+
+/// Extracts the doc comments from a list of attributes as plain text.
+///
+/// Each line has the single leading space of the `/// text` convention
+/// removed, and blank lines between paragraphs are kept. Leading and trailing
+/// blank lines are dropped.
+pub fn extract(attrs: &[Attribute]) -> String {
+    let mut lines = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("doc")) {
+        let doc = match attr.parse_meta() {
+            Ok(Meta::NameValue(nv)) => match nv.lit {
+                Lit::Str(s) => s.value(),
+                _ => continue,
+            },
+            _ => continue,
+        };
+
+        // block comments can span multiple lines in one attribute
+        for line in doc.split('\n') {
+            let line = line.strip_prefix(' ').unwrap_or(line);
+            lines.push(line.trim_end().to_owned());
+        }
+    }
+
+    let start = lines.iter().position(|l| !l.is_empty()).unwrap_or(lines.len());
+    let end = lines.iter().rposition(|l| !l.is_empty()).map_or(start, |i| i + 1);
+
+    lines[start..end].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn extract_paragraphs() {
+        let item: syn::DeriveInput = syn::parse_quote! {
+            /// First line.
+            ///   Indented.
+            ///
+            /// Second paragraph.
+            #[derive(Clone)]
+            struct Documented;
+        };
+
+        assert_eq!(
+            extract(&item.attrs),
+            "First line.\n  Indented.\n\nSecond paragraph.",
+        );
+    }
+}
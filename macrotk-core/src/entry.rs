@@ -0,0 +1,142 @@
+//! Wrappers for proc macro entry points.
+//!
+//! Setting the `MACROTK_DEBUG` environment variable to anything other than
+//! `0` while building makes these write each expansion's input and output to
+//! files under `target/macrotk-expansions/`.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use syn::Error;
+
+use proc_macro2::TokenStream;
+
+use crate::errors::render_result;
+use crate::warnings;
+
+static EXPANSION_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Runs a derive or function-like macro.
+///
+/// Errors are rendered as `compile_error!` invocations, and pending
+/// [`warnings`] as items after the output. Function-like macros that expand
+/// to an expression should render them with [`warnings::take`] themselves.
+pub fn expand<F>(name: &str, input: TokenStream, f: F) -> TokenStream
+where F:
+    FnOnce(TokenStream) -> Result<TokenStream, Error>,
+{
+    warnings::take();
+
+    let mut output = render_result(f(input.clone()));
+    output.extend(warnings::take());
+
+    if enabled() {
+        dump(name, &[("input", &input), ("output", &output)]);
+    }
+
+    output
+}
+
+/// Runs an attribute macro.
+///
+/// Errors are rendered as `compile_error!` invocations, and pending
+/// [`warnings`] as items after the output.
+pub fn expand_attribute<F>(
+    name: &str,
+    attr: TokenStream,
+    item: TokenStream,
+    f: F,
+) -> TokenStream
+where F:
+    FnOnce(TokenStream, TokenStream) -> Result<TokenStream, Error>,
+{
+    warnings::take();
+
+    let mut output = render_result(f(attr.clone(), item.clone()));
+    output.extend(warnings::take());
+
+    if enabled() {
+        dump(name, &[("attr", &attr), ("item", &item), ("output", &output)]);
+    }
+
+    output
+}
+
+fn enabled() -> bool {
+    std::env::var("MACROTK_DEBUG")
+        .map(|v| !v.is_empty() && v != "0")
+        .unwrap_or(false)
+}
+
+fn dump(name: &str, sections: &[(&str, &TokenStream)]) {
+    let dir = target_dir().join("macrotk-expansions");
+
+    let krate = std::env::var("CARGO_CRATE_NAME")
+        .unwrap_or_else(|_| String::from("unknown"));
+    let count = EXPANSION_COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    let mut contents = String::new();
+    for (title, tokens) in sections {
+        contents.push_str(&format!("// {}\n{}\n\n", title, render(tokens)));
+    }
+
+    // this is a debugging aid, so failing to write shouldn't fail the build
+    let _ = fs::create_dir_all(&dir).and_then(|_| {
+        fs::write(dir.join(format!("{}-{}-{}.rs", krate, name, count)), contents)
+    });
+}
+
+fn target_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("CARGO_TARGET_DIR") {
+        return dir.into();
+    }
+
+    let manifest_dir: PathBuf = std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(Into::into)
+        .unwrap_or_default();
+
+    // workspace members share the target dir of the workspace root
+    manifest_dir.ancestors()
+        .map(|dir| dir.join("target"))
+        .find(|dir| dir.is_dir())
+        .unwrap_or_else(|| manifest_dir.join("target"))
+}
+
+#[cfg(feature = "debug")]
+fn render(tokens: &TokenStream) -> String {
+    crate::debug::pretty(tokens)
+}
+
+#[cfg(not(feature = "debug"))]
+fn render(tokens: &TokenStream) -> String {
+    tokens.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proc_macro2::Span;
+
+    use quote::quote;
+
+    #[test]
+    pub fn expand_error() {
+        let output = expand("test", quote!(struct Foo;), |_| {
+            Err(Error::new(Span::call_site(), "bad input"))
+        });
+
+        assert_eq!(
+            output.to_string(),
+            quote!(compile_error! { "bad input" }).to_string(),
+        );
+    }
+
+    #[test]
+    pub fn expand_ok() {
+        let output = expand("test", quote!(struct Foo;), Ok);
+
+        assert_eq!(output.to_string(), quote!(struct Foo;).to_string());
+    }
+}
@@ -0,0 +1,148 @@
+//! Helpers for generating matches over enum variants.
+
+use syn::{Field, Fields, Ident, Variant};
+
+use quote::{format_ident, quote};
+
+use proc_macro2::TokenStream;
+
+/// A variant with every field bound to a local.
+pub struct VariantBinding<'a> {
+    pub variant: &'a Variant,
+    /// The pattern that binds the fields, like `Enum::Variant(__binding_0)`.
+    pub pattern: TokenStream,
+    /// Each field alongside the local it is bound to, in declaration order.
+    pub bindings: Vec<(&'a Field, Ident)>,
+}
+
+impl<'a> VariantBinding<'a> {
+    /// Binds every field of a variant of the enum `enum_name`.
+    ///
+    /// Fields are bound to `__binding_0`, `__binding_1` and so on, whether
+    /// they are named or not, so the locals never shadow anything.
+    pub fn new(enum_name: &Ident, variant: &'a Variant) -> VariantBinding<'a> {
+        let name = &variant.ident;
+
+        let bindings = variant.fields.iter()
+            .enumerate()
+            .map(|(i, field)| (field, format_ident!("__binding_{}", i)))
+            .collect::<Vec<_>>();
+        let locals = bindings.iter().map(|(_, local)| local);
+
+        let pattern = match &variant.fields {
+            Fields::Named(_) => {
+                let names = bindings.iter().map(|(field, _)| &field.ident);
+                quote!(#enum_name::#name { #(#names: #locals),* })
+            }
+            Fields::Unnamed(_) => quote!(#enum_name::#name(#(#locals),*)),
+            Fields::Unit => quote!(#enum_name::#name),
+        };
+
+        VariantBinding {
+            variant,
+            pattern,
+            bindings,
+        }
+    }
+}
+
+/// Generates a match arm for every variant of an enum.
+///
+/// `f` generates the body of each arm.
+#[cfg(feature = "items")]
+pub fn match_arms<F>(item: &syn::ItemEnum, f: F) -> TokenStream
+where F:
+    FnMut(&VariantBinding) -> TokenStream,
+{
+    match_variants(&item.ident, &item.variants, f)
+}
+
+/// Generates a match arm for each variant of the enum `enum_name`.
+///
+/// This is useful with the variants of a [`DataEnum`](syn::DataEnum).
+pub fn match_variants<'a, I, F>(enum_name: &Ident, variants: I, mut f: F) -> TokenStream
+where
+    I: IntoIterator<Item = &'a Variant>,
+    F: FnMut(&VariantBinding) -> TokenStream,
+{
+    variants.into_iter()
+        .map(|variant| {
+            let binding = VariantBinding::new(enum_name, variant);
+            let body = f(&binding);
+            let pattern = &binding.pattern;
+
+            quote!(#pattern => { #body })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use syn::{parse_quote, DataEnum};
+
+    fn variants() -> DataEnum {
+        let item: syn::DeriveInput = parse_quote! {
+            enum Shape {
+                Empty,
+                Circle(f32),
+                Rect { w: f32, h: f32 },
+            }
+        };
+
+        match item.data {
+            syn::Data::Enum(data) => data,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    pub fn bind_variants() {
+        let data = variants();
+        let name: Ident = parse_quote!(Shape);
+        let bindings = data.variants.iter()
+            .map(|variant| VariantBinding::new(&name, variant))
+            .collect::<Vec<_>>();
+
+        assert_eq!(bindings[0].pattern.to_string(), quote!(Shape::Empty).to_string());
+        assert!(bindings[0].bindings.is_empty());
+
+        assert_eq!(
+            bindings[1].pattern.to_string(),
+            quote!(Shape::Circle(__binding_0)).to_string(),
+        );
+
+        assert_eq!(
+            bindings[2].pattern.to_string(),
+            quote!(Shape::Rect { w: __binding_0, h: __binding_1 }).to_string(),
+        );
+        let fields = bindings[2].bindings.iter()
+            .map(|(field, local)| (field.ident.as_ref().unwrap().to_string(), local.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(fields, vec![
+            ("w".to_string(), "__binding_0".to_string()),
+            ("h".to_string(), "__binding_1".to_string()),
+        ]);
+    }
+
+    #[test]
+    pub fn match_every_variant() {
+        let data = variants();
+        let name: Ident = parse_quote!(Shape);
+
+        let mut seen = Vec::new();
+        let arms = match_variants(&name, &data.variants, |binding| {
+            seen.push(binding.variant.ident.to_string());
+            let count = binding.bindings.len();
+            quote!(#count)
+        });
+
+        assert_eq!(seen, vec!["Empty", "Circle", "Rect"]);
+        assert_eq!(arms.to_string(), quote! {
+            Shape::Empty => { 0usize }
+            Shape::Circle(__binding_0) => { 1usize }
+            Shape::Rect { w: __binding_0, h: __binding_1 } => { 2usize }
+        }.to_string());
+    }
+}
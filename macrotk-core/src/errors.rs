@@ -0,0 +1,120 @@
+//! Helpers for reporting errors.
+//!
+//! Everything here works on [`proc_macro2`] types, so it can be used outside
+//! of a proc macro, like in build scripts and unit tests.
+
+use std::fmt::Display;
+
+use syn::Error;
+
+use quote::ToTokens;
+
+use proc_macro2::TokenStream;
+
+/// Combines errors into one, or `None` if there are none.
+pub fn combine<I>(errors: I) -> Option<Error>
+where I:
+    IntoIterator<Item = Error>,
+{
+    errors.into_iter()
+        .fold(None, |combined, err| match combined {
+            Some(mut combined) => {
+                Error::combine(&mut combined, err);
+                Some(combined)
+            }
+            None => Some(err),
+        })
+}
+
+/// Renders errors as `compile_error!` invocations.
+pub fn render_errors_to_tokens<I>(errors: I) -> TokenStream
+where I:
+    IntoIterator<Item = Error>,
+{
+    combine(errors)
+        .map(Error::into_compile_error)
+        .unwrap_or_default()
+}
+
+/// Renders a result, turning an error into `compile_error!` invocations.
+pub fn render_result(result: Result<TokenStream, Error>) -> TokenStream {
+    result.unwrap_or_else(Error::into_compile_error)
+}
+
+/// Gives any error a location, as in `.with_span(&lit)?`.
+pub trait WithSpan<T> {
+    /// Turns the error into a [`syn::Error`] spanning `tokens`.
+    fn with_span<S>(self, tokens: &S) -> Result<T, Error>
+    where S:
+        ToTokens + ?Sized;
+}
+
+impl<T, E> WithSpan<T> for Result<T, E>
+where E:
+    Display,
+{
+    fn with_span<S>(self, tokens: &S) -> Result<T, Error>
+    where S:
+        ToTokens + ?Sized,
+    {
+        self.map_err(|err| Error::new_spanned(tokens, err))
+    }
+}
+
+/// Adds a breadcrumb of what was being parsed to errors, as in
+/// `.context("while parsing `retry` options")?`.
+pub trait Context<T> {
+    /// Prefixes every message of the error with `context`, keeping their
+    /// spans.
+    fn context<C>(self, context: C) -> Result<T, Error>
+    where C:
+        Display;
+}
+
+impl<T> Context<T> for Result<T, Error> {
+    fn context<C>(self, context: C) -> Result<T, Error>
+    where C:
+        Display,
+    {
+        self.map_err(|err| {
+            let errors = err.into_iter()
+                .map(|err| Error::new(err.span(), format!("{}: {}", context, err)));
+
+            combine(errors).expect("an error has at least one message")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::meta::Meta;
+
+    #[test]
+    pub fn render_outside_proc_macro() {
+        let errors = ["a = 1", "b"]
+            .iter()
+            .filter_map(|s| syn::parse_str::<Meta<syn::LitStr>>(s).err());
+
+        let rendered = render_errors_to_tokens(errors).to_string();
+
+        assert_eq!(rendered.matches("compile_error").count(), 2);
+    }
+
+    #[test]
+    pub fn context_breadcrumbs() {
+        let lit: syn::LitStr = syn::parse_str("\"x\"").unwrap();
+
+        let err = lit.value()
+            .parse::<u32>()
+            .with_span(&lit)
+            .context("while parsing `retry` options")
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "while parsing `retry` options: invalid digit found in string",
+        );
+    }
+}
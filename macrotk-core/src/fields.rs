@@ -0,0 +1,123 @@
+//! Helpers for reading helper attributes off of fields.
+
+use syn::{Data, DeriveInput, Error, Field};
+
+use crate::errors;
+use crate::meta::{FromMeta, MetaList};
+
+/// Parses the helper attribute `attr` on every field of an item.
+///
+/// Fields of every variant are visited for enums. Fields without the
+/// attribute are parsed from an empty list, so `T` decides whether that is
+/// an error. Errors from every field are combined into one.
+///
+/// This returns a `Vec` rather than an iterator: every field has to be
+/// parsed before the combined error is known, so a lazy iterator could only
+/// report errors one at a time.
+pub fn fields_with_meta<'a, T>(
+    item: &'a DeriveInput,
+    attr: &str,
+) -> Result<Vec<(&'a Field, T)>, Error>
+where T:
+    FromMeta,
+{
+    let fields: Vec<&Field> = match &item.data {
+        Data::Struct(s) => s.fields.iter().collect(),
+        Data::Enum(e) => e.variants.iter().flat_map(|v| v.fields.iter()).collect(),
+        Data::Union(u) => u.fields.named.iter().collect(),
+    };
+
+    let mut parsed = Vec::with_capacity(fields.len());
+    let mut errors = Vec::new();
+
+    for field in fields {
+        let result = MetaList::from_attributes(&field.attrs, attr)
+            .and_then(|list| T::from_meta(&list.into()));
+
+        match result {
+            Ok(opts) => parsed.push((field, opts)),
+            Err(err) => errors.push(err),
+        }
+    }
+
+    match errors::combine(errors) {
+        Some(err) => Err(err),
+        None => Ok(parsed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use syn::{parse_quote, LitStr};
+
+    /// A field's name, if it has one, and its options.
+    type Parsed = (Option<String>, Vec<String>);
+
+    fn parse(item: &DeriveInput) -> Result<Vec<Parsed>, Error> {
+        let fields = fields_with_meta::<Vec<LitStr>>(item, "opt")?;
+
+        Ok(fields.into_iter()
+            .map(|(field, opts)| {
+                let ident = field.ident.as_ref().map(ToString::to_string);
+                (ident, opts.iter().map(LitStr::value).collect())
+            })
+            .collect())
+    }
+
+    #[test]
+    pub fn named_fields() {
+        let item: DeriveInput = parse_quote! {
+            struct Foo {
+                #[opt("a", "b")]
+                a: u32,
+                b: u32,
+                #[other("c")]
+                #[opt("c")]
+                c: u32,
+            }
+        };
+
+        assert_eq!(parse(&item).unwrap(), vec![
+            (Some("a".into()), vec!["a".into(), "b".into()]),
+            (Some("b".into()), vec![]),
+            (Some("c".into()), vec!["c".into()]),
+        ]);
+    }
+
+    #[test]
+    pub fn tuple_fields() {
+        let item: DeriveInput = parse_quote! {
+            struct Foo(#[opt("a")] u32, u32);
+        };
+
+        assert_eq!(parse(&item).unwrap(), vec![
+            (None, vec!["a".into()]),
+            (None, vec![]),
+        ]);
+    }
+
+    #[test]
+    pub fn unit_fields() {
+        let item: DeriveInput = parse_quote!(struct Foo;);
+
+        assert_eq!(parse(&item).unwrap(), vec![]);
+    }
+
+    #[test]
+    pub fn errors_combined() {
+        let item: DeriveInput = parse_quote! {
+            struct Foo {
+                #[opt(1)]
+                a: u32,
+                #[opt("b")]
+                b: u32,
+                #[opt(true)]
+                c: u32,
+            }
+        };
+
+        assert_eq!(parse(&item).unwrap_err().into_iter().count(), 2);
+    }
+}
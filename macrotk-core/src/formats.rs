@@ -0,0 +1,104 @@
+//! Values written in other formats inside string literals.
+
+use serde::de::DeserializeOwned;
+
+use syn::{Error, Lit, LitStr};
+
+use crate::meta::{FromMeta, MetaValue};
+
+/// A value deserialized from JSON in a string literal, as in
+/// `config = r#"{"retries": 3}"#`.
+#[cfg(feature = "json")]
+#[derive(Clone, Debug, Default)]
+pub struct Json<T>(pub T);
+
+#[cfg(feature = "json")]
+impl<T> FromMeta for Json<T>
+where T:
+    DeserializeOwned,
+{
+    fn from_meta(meta: &MetaValue) -> Result<Json<T>, Error> {
+        let lit = string_literal(meta)?;
+
+        serde_json::from_str(&lit.value())
+            .map(Json)
+            .map_err(|err| Error::new(lit.span(), format!("invalid JSON: {}", err)))
+    }
+}
+
+/// A value deserialized from TOML in a string literal, as in
+/// `config = "retries = 3\n[limits]\nmax = 10"`.
+#[cfg(feature = "toml")]
+#[derive(Clone, Debug, Default)]
+pub struct Toml<T>(pub T);
+
+#[cfg(feature = "toml")]
+impl<T> FromMeta for Toml<T>
+where T:
+    DeserializeOwned,
+{
+    fn from_meta(meta: &MetaValue) -> Result<Toml<T>, Error> {
+        let lit = string_literal(meta)?;
+
+        ::toml::from_str(&lit.value())
+            .map(Toml)
+            .map_err(|err| Error::new(lit.span(), format!("invalid TOML: {}", err)))
+    }
+}
+
+fn string_literal(meta: &MetaValue) -> Result<&LitStr, Error> {
+    match meta.literal()? {
+        Lit::Str(lit) => Ok(lit),
+        lit => Err(Error::new(lit.span(), "expected str literal")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde::Deserialize;
+
+    use syn::parse::Parser;
+
+    use crate::meta::MetaList;
+
+    #[derive(Deserialize)]
+    struct Retry {
+        retries: u32,
+    }
+
+    /// Gets `config`, checking that an error points at its literal.
+    fn config<T>(attr: &str) -> Result<T, Error>
+    where T:
+        FromMeta,
+    {
+        let list = MetaList::parse_root_attr.parse_str(attr).unwrap();
+        let lit = list.find("config").unwrap().value().literal().unwrap().span();
+
+        list.get::<T>("config").unwrap().inspect_err(|err| {
+            assert_eq!(err.span().start(), lit.start());
+            assert_eq!(err.span().end(), lit.end());
+        })
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    pub fn json() {
+        let retry = config::<Json<Retry>>(r##"config = r#"{ "retries": 3 }"#"##).unwrap();
+        assert_eq!(retry.0.retries, 3);
+
+        let err = config::<Json<Retry>>(r#"name = "x", config = "{ retries""#).err().unwrap();
+        assert!(err.to_string().starts_with("invalid JSON: "));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    pub fn toml() {
+        let retry = config::<Toml<Retry>>(r#"config = "retries = 3""#).unwrap();
+        assert_eq!(retry.0.retries, 3);
+
+        let err = config::<Toml<Retry>>(r#"name = "x", config = "retries = ""#).err().unwrap();
+        assert!(err.to_string().starts_with("invalid TOML: "));
+    }
+}
@@ -0,0 +1,285 @@
+//! Helpers for rewriting and wrapping functions.
+
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    Block, Error, Expr, FnArg, GenericParam, Ident, ItemFn, Pat, PatIdent, Path,
+    Stmt, Token,
+};
+
+use quote::{format_ident, quote};
+
+use crate::attrs::{partition_attrs, AttrPolicy};
+
+/// Renames a function, returning its old name.
+pub fn rename(item: &mut ItemFn, ident: Ident) -> Ident {
+    std::mem::replace(&mut item.sig.ident, ident)
+}
+
+/// Inserts statements at the start of a function's body.
+pub fn prepend_stmts<I>(item: &mut ItemFn, stmts: I)
+where I:
+    IntoIterator<Item = Stmt>,
+{
+    let body = std::mem::take(&mut item.block.stmts);
+
+    item.block.stmts.extend(stmts);
+    item.block.stmts.extend(body);
+}
+
+/// Inserts statements at the end of a function's body.
+///
+/// The original body is evaluated first and its value is returned after the
+/// statements run. An early `return` from the original body skips them.
+pub fn append_stmts<I>(item: &mut ItemFn, stmts: I)
+where I:
+    IntoIterator<Item = Stmt>,
+{
+    let body = std::mem::take(&mut item.block.stmts);
+    let stmts = stmts.into_iter();
+    let ret = format_ident!("__macrotk_ret");
+
+    let block: Block = syn::parse_quote! {
+        {
+            let #ret = { #(#body)* };
+            #(#stmts)*
+            #ret
+        }
+    };
+
+    item.block.stmts = block.stmts;
+}
+
+/// A function split into an inner function holding the original body and an
+/// outer function that calls it.
+pub struct Wrapped {
+    /// The renamed original function.
+    pub inner: ItemFn,
+    /// The wrapper, with the original function's name and signature.
+    pub outer: ItemFn,
+}
+
+/// Wraps a function, renaming the original to `inner`.
+///
+/// Methods with a receiver are called as `Self::inner`; anything else is
+/// called as `inner`. Attributes are split with
+/// [`AttrPolicy::default_for`]. See [`wrap_with`] for more control.
+pub fn wrap(item: ItemFn, inner: Ident) -> Result<Wrapped, Error> {
+    let path: Path = if item.sig.receiver().is_some() {
+        syn::parse_quote!(Self::#inner)
+    } else {
+        inner.clone().into()
+    };
+
+    wrap_with(item, inner, path, AttrPolicy::default_for)
+}
+
+/// Wraps a function, renaming the original to `inner` and calling it through
+/// `path`.
+///
+/// The outer function keeps the original's visibility, asyncness, unsafety,
+/// generics and where clause, and forwards every argument. Arguments bound
+/// with patterns are given generated names in the outer function. The inner
+/// function is made private.
+pub fn wrap_with<F>(
+    item: ItemFn,
+    inner: Ident,
+    path: Path,
+    policy: F,
+) -> Result<Wrapped, Error>
+where F:
+    FnMut(&syn::Attribute) -> AttrPolicy,
+{
+    if let Some(variadic) = &item.sig.variadic {
+        return Err(Error::new(variadic.span(), "cannot wrap variadic functions"));
+    }
+
+    let ItemFn { attrs, vis, sig, block } = item;
+    let attrs = partition_attrs(attrs, policy);
+
+    let mut inner_sig = sig.clone();
+    inner_sig.ident = inner;
+
+    let mut outer_sig = sig;
+    let mut args: Punctuated<Expr, Token![,]> = Punctuated::new();
+
+    for (i, input) in outer_sig.inputs.iter_mut().enumerate() {
+        match input {
+            FnArg::Receiver(receiver) => {
+                // `mut self` only matters to the body, but `&mut self` is
+                // part of the signature
+                if receiver.reference.is_none() {
+                    receiver.mutability = None;
+                }
+                args.push(syn::parse_quote!(self));
+            }
+            FnArg::Typed(arg) => {
+                let ident = match &*arg.pat {
+                    Pat::Ident(pat) if pat.subpat.is_none() => pat.ident.clone(),
+                    pat => format_ident!("__arg{}", i, span = pat.span()),
+                };
+
+                *arg.pat = Pat::Ident(PatIdent {
+                    attrs: Vec::new(),
+                    by_ref: None,
+                    mutability: None,
+                    ident: ident.clone(),
+                    subpat: None,
+                });
+                args.push(syn::parse_quote!(#ident));
+            }
+        }
+    }
+
+    // lifetimes can't always be given explicitly, so let inference
+    // handle them
+    let generic_args = outer_sig.generics.params.iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(ty) => Some(&ty.ident),
+            GenericParam::Const(c) => Some(&c.ident),
+            GenericParam::Lifetime(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let turbofish = if generic_args.is_empty() {
+        quote!()
+    } else {
+        quote!(::<#(#generic_args),*>)
+    };
+
+    let mut call = quote!(#path #turbofish(#args));
+    if outer_sig.asyncness.is_some() {
+        call = quote!(#call.await);
+    }
+    if outer_sig.unsafety.is_some() {
+        call = quote!(unsafe { #call });
+    }
+
+    let mut outer_attrs = attrs.generated;
+    if outer_sig.unsafety.is_some() {
+        outer_attrs.push(syn::parse_quote!(#[allow(unused_unsafe)]));
+    }
+
+    Ok(Wrapped {
+        inner: ItemFn {
+            attrs: attrs.original,
+            vis: syn::Visibility::Inherited,
+            sig: inner_sig,
+            block,
+        },
+        outer: ItemFn {
+            attrs: outer_attrs,
+            vis,
+            sig: outer_sig,
+            block: syn::parse_quote!({ #call }),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use quote::ToTokens;
+
+    fn assert_tokens<T: ToTokens>(item: &T, expected: proc_macro2::TokenStream) {
+        assert_eq!(item.to_token_stream().to_string(), expected.to_string());
+    }
+
+    #[test]
+    pub fn rename_and_splice() {
+        let mut item: ItemFn = syn::parse_quote! {
+            fn answer() -> u32 {
+                42
+            }
+        };
+
+        let old = rename(&mut item, format_ident!("question"));
+        assert_eq!(old, "answer");
+
+        prepend_stmts(&mut item, vec![syn::parse_quote!(let x = 1;)]);
+        append_stmts(&mut item, vec![syn::parse_quote!(drop(x);)]);
+
+        assert_tokens(&item, quote! {
+            fn question() -> u32 {
+                let __macrotk_ret = {
+                    let x = 1;
+                    42
+                };
+                drop(x);
+                __macrotk_ret
+            }
+        });
+    }
+
+    #[test]
+    pub fn wrap_async_generic() {
+        let item: ItemFn = syn::parse_quote! {
+            pub async fn fetch<'a, T: Clone, const N: usize>(x: &'a T, (a, b): (u8, u8)) -> T {
+                x.clone()
+            }
+        };
+
+        let wrapped = wrap(item, format_ident!("fetch_inner")).unwrap();
+
+        assert_tokens(&wrapped.inner, quote! {
+            async fn fetch_inner<'a, T: Clone, const N: usize>(x: &'a T, (a, b): (u8, u8)) -> T {
+                x.clone()
+            }
+        });
+        assert_tokens(&wrapped.outer, quote! {
+            pub async fn fetch<'a, T: Clone, const N: usize>(x: &'a T, __arg1: (u8, u8)) -> T {
+                fetch_inner::<T, N>(x, __arg1).await
+            }
+        });
+    }
+
+    #[test]
+    pub fn wrap_receivers() {
+        let item: ItemFn = syn::parse_quote! {
+            fn bump(&mut self, mut by: u32) -> u32 {
+                by += 1;
+                by
+            }
+        };
+
+        let wrapped = wrap(item, format_ident!("bump_inner")).unwrap();
+
+        assert_tokens(&wrapped.outer, quote! {
+            fn bump(&mut self, by: u32) -> u32 {
+                Self::bump_inner(self, by)
+            }
+        });
+
+        let item: ItemFn = syn::parse_quote! {
+            fn into_name(mut self) -> String {
+                self.name
+            }
+        };
+
+        let wrapped = wrap(item, format_ident!("into_name_inner")).unwrap();
+
+        assert_tokens(&wrapped.outer, quote! {
+            fn into_name(self) -> String {
+                Self::into_name_inner(self)
+            }
+        });
+    }
+
+    #[test]
+    pub fn wrap_unsafe() {
+        let item: ItemFn = syn::parse_quote! {
+            unsafe fn read(ptr: *const u8) -> u8 {
+                *ptr
+            }
+        };
+
+        let wrapped = wrap(item, format_ident!("read_inner")).unwrap();
+
+        assert_tokens(&wrapped.outer, quote! {
+            #[allow(unused_unsafe)]
+            unsafe fn read(ptr: *const u8) -> u8 {
+                unsafe { read_inner(ptr) }
+            }
+        });
+    }
+}
@@ -0,0 +1,126 @@
+//! Helpers for manipulating [`Generics`] in derive macros.
+
+use syn::{
+    GenericParam, Generics, ImplGenerics, Path, TraitBound, TraitBoundModifier,
+    Turbofish, TypeGenerics, TypeParamBound, WhereClause,
+};
+
+/// Adds a trait bound to every type parameter.
+pub fn add_trait_bound(generics: &mut Generics, bound: &Path) {
+    for param in generics.params.iter_mut() {
+        if let GenericParam::Type(ty) = param {
+            ty.bounds.push(TypeParamBound::Trait(TraitBound {
+                paren_token: None,
+                modifier: TraitBoundModifier::None,
+                lifetimes: None,
+                path: bound.clone(),
+            }));
+        }
+    }
+}
+
+/// Merges two where clauses, keeping the predicates of `a` before `b`.
+///
+/// Returns `None` if neither where clause exists.
+pub fn merge_where_clauses(
+    a: Option<&WhereClause>,
+    b: Option<&WhereClause>,
+) -> Option<WhereClause> {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            let mut merged = a.clone();
+
+            if !merged.predicates.empty_or_trailing() {
+                merged.predicates.push_punct(Default::default());
+            }
+            merged.predicates.extend(b.predicates.iter().cloned());
+
+            Some(merged)
+        }
+        (Some(clause), None) | (None, Some(clause)) => Some(clause.clone()),
+        (None, None) => None,
+    }
+}
+
+/// The generics of a type, split up for use in an `impl` block.
+///
+/// See [`split_for_impl`].
+pub struct SplitGenerics<'a> {
+    /// Goes after `impl`, as in `impl #impl_generics`.
+    pub impl_generics: ImplGenerics<'a>,
+    /// Goes after the type, as in `#name #ty_generics`.
+    pub ty_generics: TypeGenerics<'a>,
+    pub where_clause: Option<&'a WhereClause>,
+}
+
+impl<'a> SplitGenerics<'a> {
+    /// The type generics in turbofish form, for use in an expression, as in
+    /// `#name #turbofish::new()`.
+    pub fn turbofish(&self) -> Turbofish<'_> {
+        self.ty_generics.as_turbofish()
+    }
+}
+
+/// Splits generics for an `impl` block.
+///
+/// This works like [`Generics::split_for_impl`], but also yields the
+/// turbofish form of the type generics.
+pub fn split_for_impl(generics: &Generics) -> SplitGenerics<'_> {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    SplitGenerics {
+        impl_generics,
+        ty_generics,
+        where_clause,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use quote::{quote, ToTokens};
+
+    #[test]
+    pub fn add_bounds() {
+        let mut generics: Generics = syn::parse_quote!(<'a, T, U: Clone, const N: usize>);
+        add_trait_bound(&mut generics, &syn::parse_quote!(::std::fmt::Debug));
+
+        assert_eq!(
+            generics.to_token_stream().to_string(),
+            quote!(<'a, T: ::std::fmt::Debug, U: Clone + ::std::fmt::Debug, const N: usize>).to_string(),
+        );
+    }
+
+    #[test]
+    pub fn merge_where() {
+        let a: WhereClause = syn::parse_quote!(where T: Clone);
+        let b: WhereClause = syn::parse_quote!(where U: Copy,);
+
+        let merged = merge_where_clauses(Some(&a), Some(&b)).unwrap();
+        assert_eq!(
+            merged.to_token_stream().to_string(),
+            quote!(where T: Clone, U: Copy).to_string(),
+        );
+
+        let one = merge_where_clauses(None, Some(&b)).unwrap();
+        assert_eq!(one.to_token_stream().to_string(), b.to_token_stream().to_string());
+
+        assert!(merge_where_clauses(None, None).is_none());
+    }
+
+    #[test]
+    pub fn split() {
+        let mut generics: Generics = syn::parse_quote!(<'a, T: Clone>);
+        generics.where_clause = Some(syn::parse_quote!(where T: Copy));
+        let split = split_for_impl(&generics);
+
+        assert_eq!(split.impl_generics.to_token_stream().to_string(), quote!(<'a, T: Clone>).to_string());
+        assert_eq!(split.ty_generics.to_token_stream().to_string(), quote!(<'a, T>).to_string());
+        assert_eq!(split.turbofish().to_token_stream().to_string(), quote!(::<'a, T>).to_string());
+        assert_eq!(
+            split.where_clause.to_token_stream().to_string(),
+            quote!(where T: Copy).to_string(),
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! Helpers for span hygiene.
+//!
+//! A span has a location, which is where errors point, and a resolution,
+//! which decides what names resolve to. Generated code usually wants names
+//! resolved like the macro's own, so they can't clash with the user's, but
+//! errors located at the user's input.
+//!
+//! Outside of a proc macro, spans have no hygiene, so these only change
+//! locations.
+
+use syn::Ident;
+
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
+
+/// A span that resolves like local names of the macro, located at `span`.
+pub fn mixed_site(span: Span) -> Span {
+    Span::mixed_site().located_at(span)
+}
+
+/// A span that resolves like names at the macro call, located at `span`.
+pub fn call_site(span: Span) -> Span {
+    Span::call_site().located_at(span)
+}
+
+/// An identifier the user can't refer to, located at `span`.
+///
+/// Use this for temporaries in generated code.
+pub fn private_ident(name: &str, span: Span) -> Ident {
+    Ident::new(name, mixed_site(span))
+}
+
+/// Moves every token to `span`, keeping how it resolves.
+pub fn located_at(tokens: TokenStream, span: Span) -> TokenStream {
+    map_spans(tokens, &|s| s.located_at(span))
+}
+
+/// Makes every token resolve like `span`, keeping where it is.
+pub fn resolved_at(tokens: TokenStream, span: Span) -> TokenStream {
+    map_spans(tokens, &|s| s.resolved_at(span))
+}
+
+fn map_spans(tokens: TokenStream, f: &dyn Fn(Span) -> Span) -> TokenStream {
+    tokens.into_iter()
+        .map(|tt| match tt {
+            TokenTree::Group(group) => {
+                let mut new = Group::new(group.delimiter(), map_spans(group.stream(), f));
+                new.set_span(f(group.span()));
+                new.into()
+            }
+            mut tt => {
+                tt.set_span(f(tt.span()));
+                tt
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every span in a stream, including groups' own delimiter spans.
+    fn spans(tokens: TokenStream, out: &mut Vec<Span>) {
+        for tt in tokens {
+            out.push(tt.span());
+            if let TokenTree::Group(group) = tt {
+                spans(group.stream(), out);
+            }
+        }
+    }
+
+    fn starts(tokens: TokenStream) -> Vec<(usize, usize)> {
+        let mut out = Vec::new();
+        spans(tokens, &mut out);
+        out.iter().map(|s| (s.start().line, s.start().column)).collect()
+    }
+
+    #[test]
+    pub fn located_at_nested() {
+        let tokens: TokenStream = "a (b [c {d}])".parse().unwrap();
+        let target = syn::parse_str::<Ident>("\n    x").unwrap().span();
+
+        let moved = located_at(tokens, target);
+        assert_eq!(starts(moved.clone()), vec![(2, 4); 7]);
+
+        // the group's own span moves too, not only its contents
+        let group = match moved.into_iter().nth(1) {
+            Some(TokenTree::Group(group)) => group,
+            _ => unreachable!(),
+        };
+        assert_eq!(group.span_open().start(), target.start());
+    }
+
+    #[test]
+    pub fn resolved_at_keeps_location() {
+        let tokens: TokenStream = "a (b [c])".parse().unwrap();
+        let before = starts(tokens.clone());
+
+        let resolved = resolved_at(tokens, Span::mixed_site());
+        assert_eq!(starts(resolved), before);
+    }
+
+    #[test]
+    pub fn private_ident_location() {
+        let span = syn::parse_str::<Ident>("  x").unwrap().span();
+        let ident = private_ident("__tmp", span);
+
+        assert_eq!(ident, "__tmp");
+        assert_eq!(ident.span().start(), span.start());
+    }
+}
@@ -0,0 +1,174 @@
+//! Identifier helpers.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::panic::Location;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use quote::IdentFragment;
+
+use syn::Ident;
+
+use proc_macro2::Span;
+
+static GENSYM_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Generates a unique identifier for a temporary in generated code.
+///
+/// The identifier is built from `prefix`, a hash of the caller's location
+/// and a global counter, so it will not collide with user identifiers or
+/// other generated identifiers.
+///
+/// # Panics
+/// Panics if `prefix` cannot start an identifier.
+#[track_caller]
+pub fn gensym(prefix: &str) -> Ident {
+    let location = Location::caller();
+
+    let mut hasher = DefaultHasher::new();
+    location.file().hash(&mut hasher);
+    location.line().hash(&mut hasher);
+    location.column().hash(&mut hasher);
+
+    let count = GENSYM_COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    Ident::new(
+        &format!("__{}_{:08x}_{}", prefix, hasher.finish() as u32, count),
+        Span::call_site(),
+    )
+}
+
+/// Prepends `prefix` to an identifier, keeping the identifier's span.
+///
+/// Raw identifiers lose their `r#`, so `prefixed("get_", r#type)` is
+/// `get_type`.
+pub fn prefixed(prefix: &str, ident: &Ident) -> Ident {
+    Ident::new(&format!("{}{}", prefix, Fragment(ident)), ident.span())
+}
+
+/// Appends `suffix` to an identifier, keeping the identifier's span.
+///
+/// Raw identifiers lose their `r#`, so `suffixed(r#type, "_builder")` is
+/// `type_builder`.
+pub fn suffixed(ident: &Ident, suffix: &str) -> Ident {
+    Ident::new(&format!("{}{}", Fragment(ident), suffix), ident.span())
+}
+
+/// Converts a `CamelCase` identifier to `snake_case`, like a variant name
+/// to the name it is written as in an attribute.
+///
+/// A run of capitals is one word, so `HTTPServer` is `http_server`. Raw
+/// identifiers lose their `r#`.
+pub fn snake_case(ident: &Ident) -> String {
+    let name = Fragment(ident).to_string();
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut out = String::with_capacity(name.len());
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next = chars.get(i + 1).copied();
+
+            // a word starts after a lowercase letter or digit, or at the last
+            // capital of a run that goes on in lowercase
+            let starts_word = prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next.is_some_and(char::is_lowercase));
+
+            if starts_word {
+                out.push('_');
+            }
+        }
+
+        out.extend(c.to_lowercase());
+    }
+
+    out
+}
+
+struct Fragment<'a>(&'a Ident);
+
+impl std::fmt::Display for Fragment<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        IdentFragment::fmt(self.0, f)
+    }
+}
+
+/// Formats an identifier with an explicit span.
+///
+/// This works like [`quote::format_ident!`], but the span comes first so it
+/// can't be forgotten.
+#[macro_export]
+macro_rules! format_ident_at {
+    ($span:expr, $fmt:expr $(, $arg:expr)* $(,)?) => {
+        $crate::quote::format_ident!($fmt $(, $arg)*, span = $span)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use quote::format_ident;
+
+    #[test]
+    pub fn gensym_unique() {
+        let idents = (0..2).map(|_| gensym("tmp")).collect::<Vec<_>>();
+
+        assert_ne!(idents[0], idents[1]);
+        assert!(idents.iter().all(|ident| ident.to_string().starts_with("__tmp_")));
+
+        // generated identifiers resolve at the call site
+        let span = Span::call_site();
+        assert_eq!(format!("{:?}", idents[0].span()), format!("{:?}", span));
+    }
+
+    #[test]
+    pub fn snake_case_words() {
+        let cases = [
+            ("Simple", "simple"),
+            ("TwoWords", "two_words"),
+            ("HTTPServer", "http_server"),
+            ("IOError", "io_error"),
+            ("ServeHTTP", "serve_http"),
+            ("Http2Server", "http2_server"),
+            ("V2", "v2"),
+            ("Already_Snake", "already_snake"),
+        ];
+
+        for (ident, expected) in cases {
+            assert_eq!(snake_case(&format_ident!("{}", ident)), expected);
+        }
+    }
+
+    #[test]
+    pub fn affixes() {
+        let ident: Ident = syn::parse_str("  name").unwrap();
+
+        let prefixed = prefixed("get_", &ident);
+        assert_eq!(prefixed, "get_name");
+        assert_eq!(prefixed.span().start(), ident.span().start());
+
+        let suffixed = suffixed(&ident, "_builder");
+        assert_eq!(suffixed, "name_builder");
+        assert_eq!(suffixed.span().start(), ident.span().start());
+    }
+
+    #[test]
+    pub fn affixes_raw() {
+        let ident: Ident = syn::parse_str("r#type").unwrap();
+
+        assert_eq!(prefixed("prefix_", &ident), "prefix_type");
+        assert_eq!(suffixed(&ident, "_builder"), "type_builder");
+        assert_eq!(snake_case(&ident), "type");
+    }
+
+    #[test]
+    pub fn format_at_span() {
+        let span = syn::parse_str::<Ident>("  field").unwrap().span();
+        let ident = crate::format_ident_at!(span, "{}_{}", "get", "field");
+
+        assert_eq!(ident, "get_field");
+        assert_eq!(ident.span().start(), span.start());
+    }
+}
@@ -0,0 +1,178 @@
+//! A builder for generating `impl` blocks.
+
+use syn::{Generics, Path, WherePredicate};
+
+use quote::{quote, ToTokens};
+
+use proc_macro2::TokenStream;
+
+use crate::generics::{add_trait_bound, merge_where_clauses, split_for_impl};
+
+/// Builds an `impl` block for a type.
+///
+/// Generics given with [`ImplBuilder::with_generics`] are split between the
+/// `impl`, the type and the where clause automatically, so `self_ty` should
+/// be the bare type name.
+///
+/// Paths given to the builder are used as-is, so pass fully-qualified paths
+/// (`::std::fmt::Debug`, or one from [`crate_path`](crate::crate_path)) to
+/// keep the generated code from depending on what is in scope.
+#[derive(Clone)]
+pub struct ImplBuilder {
+    trait_path: Option<Path>,
+    self_ty: TokenStream,
+    generics: Generics,
+    items: Vec<TokenStream>,
+}
+
+impl ImplBuilder {
+    /// Starts a trait impl.
+    pub fn new<T>(trait_path: Path, self_ty: T) -> ImplBuilder
+    where T:
+        ToTokens,
+    {
+        ImplBuilder {
+            trait_path: Some(trait_path),
+            self_ty: self_ty.into_token_stream(),
+            generics: Generics::default(),
+            items: Vec::new(),
+        }
+    }
+
+    /// Starts an inherent impl.
+    pub fn inherent<T>(self_ty: T) -> ImplBuilder
+    where T:
+        ToTokens,
+    {
+        ImplBuilder {
+            trait_path: None,
+            self_ty: self_ty.into_token_stream(),
+            generics: Generics::default(),
+            items: Vec::new(),
+        }
+    }
+
+    /// Sets the generics of the type.
+    ///
+    /// Predicates already added with [`ImplBuilder::predicate`] are kept,
+    /// after the ones from `generics`.
+    pub fn with_generics(mut self, generics: &Generics) -> ImplBuilder {
+        let where_clause = merge_where_clauses(
+            generics.where_clause.as_ref(),
+            self.generics.where_clause.as_ref(),
+        );
+
+        self.generics = generics.clone();
+        self.generics.where_clause = where_clause;
+        self
+    }
+
+    /// Bounds every type parameter by a trait.
+    pub fn bound_params(mut self, bound: &Path) -> ImplBuilder {
+        add_trait_bound(&mut self.generics, bound);
+        self
+    }
+
+    /// Adds a predicate to the where clause.
+    pub fn predicate(mut self, predicate: WherePredicate) -> ImplBuilder {
+        self.generics.make_where_clause().predicates.push(predicate);
+        self
+    }
+
+    /// Adds a method.
+    pub fn method<T>(self, method: T) -> ImplBuilder
+    where T:
+        ToTokens,
+    {
+        self.item(method)
+    }
+
+    /// Adds any associated item, like a type or a constant.
+    pub fn item<T>(mut self, item: T) -> ImplBuilder
+    where T:
+        ToTokens,
+    {
+        self.items.push(item.into_token_stream());
+        self
+    }
+
+    /// Generates the `impl` block.
+    pub fn build(&self) -> TokenStream {
+        self.to_token_stream()
+    }
+}
+
+impl ToTokens for ImplBuilder {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let split = split_for_impl(&self.generics);
+        let impl_generics = &split.impl_generics;
+        let ty_generics = &split.ty_generics;
+        let where_clause = split.where_clause;
+
+        let trait_for = self.trait_path.as_ref().map(|path| quote!(#path for));
+        let self_ty = &self.self_ty;
+        let items = &self.items;
+
+        tokens.extend(quote! {
+            #[automatically_derived]
+            impl #impl_generics #trait_for #self_ty #ty_generics #where_clause {
+                #(#items)*
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use syn::parse_quote;
+
+    #[test]
+    pub fn inherent_impl() {
+        let tokens = ImplBuilder::inherent(quote!(Foo))
+            .method(quote!(fn new() -> Foo { Foo }))
+            .build();
+
+        assert_eq!(tokens.to_string(), quote! {
+            #[automatically_derived]
+            impl Foo {
+                fn new() -> Foo { Foo }
+            }
+        }.to_string());
+    }
+
+    #[test]
+    pub fn trait_impl() {
+        let generics: Generics = parse_quote!(<'a, T>);
+        let tokens = ImplBuilder::new(parse_quote!(::std::fmt::Debug), quote!(Foo))
+            .with_generics(&generics)
+            .bound_params(&parse_quote!(::std::fmt::Debug))
+            .item(quote!(type Output = ();))
+            .build();
+
+        assert_eq!(tokens.to_string(), quote! {
+            #[automatically_derived]
+            impl<'a, T: ::std::fmt::Debug> ::std::fmt::Debug for Foo<'a, T> {
+                type Output = ();
+            }
+        }.to_string());
+    }
+
+    #[test]
+    pub fn merged_where_clause() {
+        let mut generics: Generics = parse_quote!(<T, U>);
+        generics.where_clause = Some(parse_quote!(where T: Clone));
+
+        let tokens = ImplBuilder::inherent(quote!(Foo))
+            .predicate(parse_quote!(U: Copy))
+            .with_generics(&generics)
+            .predicate(parse_quote!(T: Send))
+            .build();
+
+        assert_eq!(tokens.to_string(), quote! {
+            #[automatically_derived]
+            impl<T, U> Foo<T, U> where T: Clone, U: Copy, T: Send {}
+        }.to_string());
+    }
+}
@@ -5,4 +5,41 @@ pub extern crate syn;
 
 pub use proc_macro2::Span;
 
+pub use fields::fields_with_meta;
+pub use ident::gensym;
+pub use paths::crate_path;
+
+pub mod apply;
+pub mod attrs;
+pub mod compat;
+#[cfg(feature = "toml")]
+pub mod config;
+pub mod docs;
+pub mod entry;
+pub mod enums;
+pub mod errors;
+pub mod fields;
+#[cfg(feature = "items")]
+pub mod funcs;
+pub mod generics;
+pub mod hygiene;
+pub mod ident;
+pub mod impls;
+pub mod merge;
 pub mod meta;
+mod paths;
+#[doc(hidden)]
+pub mod runtime;
+pub mod schema;
+#[cfg(feature = "items")]
+pub mod structs;
+pub mod tokens;
+pub mod warnings;
+#[cfg(feature = "debug")]
+pub mod debug;
+#[cfg(feature = "serde")]
+pub mod de;
+#[cfg(feature = "darling")]
+pub mod darling;
+#[cfg(any(feature = "json", feature = "toml"))]
+pub mod formats;
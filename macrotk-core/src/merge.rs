@@ -0,0 +1,99 @@
+//! Layering options on top of each other.
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+use syn::{Lifetime, LitStr};
+
+/// Types that can be layered, like crate-level defaults under item-level
+/// attributes.
+///
+/// This can be derived for structs, which merge field by field, and enums,
+/// where the later value always wins.
+pub trait MergeMeta {
+    /// Layers `other` on top of `self`, so the values that are set in
+    /// `other` win.
+    fn merge(self, other: Self) -> Self;
+}
+
+impl<T> MergeMeta for Option<T>
+where T:
+    MergeMeta,
+{
+    fn merge(self, other: Option<T>) -> Option<T> {
+        match (self, other) {
+            (Some(a), Some(b)) => Some(a.merge(b)),
+            (a, None) => a,
+            (None, b) => b,
+        }
+    }
+}
+
+impl<T> MergeMeta for Box<T>
+where T:
+    MergeMeta,
+{
+    fn merge(self, other: Box<T>) -> Box<T> {
+        Box::new((*self).merge(*other))
+    }
+}
+
+impl<T> MergeMeta for Rc<T>
+where T:
+    MergeMeta + Clone,
+{
+    fn merge(self, other: Rc<T>) -> Rc<T> {
+        Rc::new(Rc::unwrap_or_clone(self).merge(Rc::unwrap_or_clone(other)))
+    }
+}
+
+impl<T> MergeMeta for Arc<T>
+where T:
+    MergeMeta + Clone,
+{
+    fn merge(self, other: Arc<T>) -> Arc<T> {
+        Arc::new(Arc::unwrap_or_clone(self).merge(Arc::unwrap_or_clone(other)))
+    }
+}
+
+/// An empty list is the default, so it doesn't override anything.
+impl<T> MergeMeta for Vec<T> {
+    fn merge(self, other: Vec<T>) -> Vec<T> {
+        if other.is_empty() { self } else { other }
+    }
+}
+
+impl MergeMeta for LitStr {
+    fn merge(self, other: LitStr) -> LitStr {
+        other
+    }
+}
+
+impl MergeMeta for Lifetime {
+    fn merge(self, other: Lifetime) -> Lifetime {
+        other
+    }
+}
+
+/// `false` is the default, so it doesn't override anything. This means a
+/// later `flag = false` can't turn off an earlier `flag`.
+impl MergeMeta for bool {
+    fn merge(self, other: bool) -> bool {
+        self || other
+    }
+}
+
+macro_rules! number_impls {
+    ($($ty:ty),*) => {$(
+        /// Zero is the default, so it doesn't override anything. This means
+        /// an explicit `0` in a later layer is dropped, even in an `Option`,
+        /// since `Option` merges what is inside it.
+        impl MergeMeta for $ty {
+            fn merge(self, other: $ty) -> $ty {
+                if other == <$ty>::default() { self } else { other }
+            }
+        }
+    )*};
+}
+
+number_impls!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
@@ -1,15 +1,30 @@
-use syn::parse::{Parse, ParseStream};
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::fmt;
+
+use syn::parse::{Parse, ParseStream, Parser};
 use syn::spanned::Spanned;
 use syn::punctuated::Punctuated;
-use syn::{Error, Path, Token, Lit, LitStr};
+use syn::{Attribute, Error, Lifetime, Path, Token, Lit, LitStr};
+
+use quote::ToTokens;
 
-use proc_macro2::Span;
+use proc_macro2::{Span, TokenStream, TokenTree};
 
 /// Types that can be parsed from a [`Meta`] list.
 pub trait FromMeta: Sized {
     fn from_meta(a: &MetaValue) -> Result<Self, Error>;
 }
 
+/// Types that can be written back as meta, the inverse of [`FromMeta`].
+///
+/// `#[derive(FromMeta)]` types with `#[macrotk(display)]` implement this,
+/// and [`Display`](std::fmt::Display) in terms of it.
+pub trait ToMeta {
+    /// The value, or `None` if it should be left out, like a [`None`].
+    fn to_meta(&self) -> Option<MetaValue>;
+}
+
 /// A meta item.
 #[derive(Clone)]
 pub enum MetaValue {
@@ -17,6 +32,13 @@ pub enum MetaValue {
     NameValue(MetaNameValue),
     List(MetaList),
     Lit(Lit),
+    /// A list that hasn't been parsed yet. See [`MetaValue::parse_lazy`].
+    Lazy(LazyList),
+    /// A lifetime, like the `'de` in `lifetime = 'de`.
+    Lifetime(Lifetime),
+    /// A name-value pair whose key isn't an identifier, like
+    /// `"x-amz-header" = "v"`.
+    StrNameValue(MetaStrNameValue),
 }
 
 impl MetaValue {
@@ -43,6 +65,24 @@ impl MetaValue {
                     "expected a naked path",
                 )
             ),
+            Self::Lazy(list) => Err(
+                Error::new(
+                    list.paren.span,
+                    "unexpected (...); expected a naked path",
+                )
+            ),
+            Self::Lifetime(lt) => Err(
+                Error::new(
+                    lt.span(),
+                    "expected a naked path",
+                )
+            ),
+            Self::StrNameValue(nv) => Err(
+                Error::new(
+                    nv.eq.span(),
+                    "unexpected =; expected a naked path",
+                )
+            ),
         }
     }
 
@@ -69,6 +109,24 @@ impl MetaValue {
                     "unexpected literal; expected =",
                 )
             ),
+            Self::Lazy(list) => Err(
+                Error::new(
+                    list.paren.span,
+                    "expected =",
+                )
+            ),
+            Self::Lifetime(lt) => Err(
+                Error::new(
+                    lt.span(),
+                    "unexpected lifetime; expected =",
+                )
+            ),
+            Self::StrNameValue(nv) => Err(
+                Error::new(
+                    nv.key.span(),
+                    "expected an identifier key",
+                )
+            ),
         }
     }
 
@@ -95,14 +153,35 @@ impl MetaValue {
                     "expected a literal",
                 )
             ),
+            Self::Lazy(list) => Err(
+                Error::new(
+                    list.name.span(),
+                    "expected a literal",
+                )
+            ),
+            Self::Lifetime(lt) => Err(
+                Error::new(
+                    lt.span(),
+                    "expected a literal",
+                )
+            ),
+            Self::StrNameValue(nv) => Err(
+                Error::new(
+                    nv.key.span(),
+                    "expected a literal",
+                )
+            ),
         }
     }
 
     /// Tries to take the value as a [`List`], returning an error if it isn't
     /// a list.
+    ///
+    /// Lazy lists are parsed by this.
     pub fn list(&self) -> Result<&MetaList, Error> {
         match self {
             Self::List(list) => Ok(list),
+            Self::Lazy(list) => list.list(),
             Self::Path(p) => Err(
                 Error::new(
                     p.span(),
@@ -121,6 +200,30 @@ impl MetaValue {
                     "expected a list",
                 )
             ),
+            Self::Lifetime(lt) => Err(
+                Error::new(
+                    lt.span(),
+                    "expected a list",
+                )
+            ),
+            Self::StrNameValue(nv) => Err(
+                Error::new(
+                    nv.eq.span(),
+                    "unexpected =; expected a list",
+                )
+            ),
+        }
+    }
+
+    /// Gets the value of the item.
+    ///
+    /// This is the right side of a [`MetaNameValue`], or the item itself for
+    /// anything else.
+    pub fn value(&self) -> &MetaValue {
+        match self {
+            Self::NameValue(nv) => &nv.value,
+            Self::StrNameValue(nv) => &nv.value,
+            item => item,
         }
     }
 
@@ -128,12 +231,159 @@ impl MetaValue {
         let path = match self {
             Self::Path(p) => p,
             Self::List(list) => list.name.as_ref()?,
+            Self::Lazy(list) => &list.name,
             Self::NameValue(nv) => &nv.name,
             _ => return None,
         };
 
         path.segments.last().map(|l| &l.ident)
     }
+
+    /// The key of the item, which is either its name or a string key.
+    pub fn key(&self) -> Option<String> {
+        match self {
+            Self::StrNameValue(nv) => Some(nv.key.value()),
+            item => item.name().map(ToString::to_string),
+        }
+    }
+
+    /// The span of the key of the item, if it has one.
+    pub fn key_span(&self) -> Option<Span> {
+        match self {
+            Self::StrNameValue(nv) => Some(nv.key.span()),
+            item => item.name().map(|name| name.span()),
+        }
+    }
+
+    /// Whether this is named `name`, or has it as a string key.
+    pub fn has_name(&self, name: &str) -> bool {
+        match self {
+            Self::StrNameValue(nv) => nv.key.value() == name,
+            item => item.name().map(|n| n == name).unwrap_or(false),
+        }
+    }
+
+    /// Whether this is named any of `names`, for matching several spellings
+    /// at once.
+    pub fn has_any_name(&self, names: &[&str]) -> bool {
+        names.iter().any(|name| self.has_name(name))
+    }
+}
+
+impl MetaValue {
+    /// Makes `value` the value of the key `name`.
+    ///
+    /// Literals become name-value pairs and root lists become named lists.
+    /// Anything else is wrapped in a named list.
+    ///
+    /// If `name` isn't an identifier, like `x-y` or the keyword `type`, it
+    /// becomes a string key.
+    ///
+    /// # Panics
+    /// Panics if `name` isn't an identifier and `value` isn't a literal,
+    /// since only name-value pairs can have string keys.
+    pub fn entry(name: &str, value: MetaValue) -> MetaValue {
+        if syn::parse_str::<syn::Ident>(name).is_err() {
+            assert!(value.is_value(), "only values can have the string key {:?}", name);
+
+            return MetaValue::StrNameValue(MetaStrNameValue {
+                key: LitStr::new(name, Span::call_site()),
+                eq: Default::default(),
+                value: Box::new(value),
+            });
+        }
+
+        let name: Path = syn::Ident::new(name, Span::call_site()).into();
+
+        match value {
+            value if value.is_value() => {
+                MetaValue::NameValue(MetaNameValue {
+                    name,
+                    eq: Default::default(),
+                    value: Box::new(value),
+                })
+            }
+            MetaValue::List(list) if list.name.is_none() => MetaValue::List(MetaList {
+                name: Some(name),
+                paren: Some(Default::default()),
+                list: list.list,
+            }),
+            value => MetaValue::List(MetaList {
+                name: Some(name),
+                paren: Some(Default::default()),
+                list: std::iter::once(value).collect(),
+            }),
+        }
+    }
+
+    /// Whether this can be the value of a name-value pair.
+    fn is_value(&self) -> bool {
+        match self {
+            MetaValue::Lit(_) | MetaValue::Lifetime(_) => true,
+            MetaValue::Path(path) => path.is_ident("none"),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for MetaValue {
+    /// Writes the meta in canonical syntax, like `name = "x", list(a, b)`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Path(path) => fmt_path(path, f),
+            Self::NameValue(nv) => {
+                fmt_path(&nv.name, f)?;
+                write!(f, " = {}", nv.value)
+            }
+            Self::List(list) => list.fmt(f),
+            Self::Lit(lit) => write!(f, "{}", lit.to_token_stream()),
+            Self::Lazy(list) => {
+                fmt_path(&list.name, f)?;
+                write!(f, "({})", list.tokens())
+            }
+            Self::Lifetime(lt) => write!(f, "{}", lt),
+            Self::StrNameValue(nv) => {
+                write!(f, "{} = {}", nv.key.to_token_stream(), nv.value)
+            }
+        }
+    }
+}
+
+impl fmt::Display for MetaList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(name) = &self.name {
+            fmt_path(name, f)?;
+            f.write_str("(")?;
+        }
+
+        for (i, meta) in self.list.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", meta)?;
+        }
+
+        if self.name.is_some() {
+            f.write_str(")")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn fmt_path(path: &Path, f: &mut fmt::Formatter) -> fmt::Result {
+    if path.leading_colon.is_some() {
+        f.write_str("::")?;
+    }
+
+    for (i, segment) in path.segments.iter().enumerate() {
+        if i > 0 {
+            f.write_str("::")?;
+        }
+        write!(f, "{}", segment.ident)?;
+    }
+
+    Ok(())
 }
 
 impl From<Lit> for MetaValue {
@@ -148,11 +398,25 @@ impl From<MetaList> for MetaValue {
     }
 }
 
-impl Parse for MetaValue {
-    fn parse(p: ParseStream) -> Result<MetaValue, Error> {
+impl MetaValue {
+    /// Parses a meta item, leaving any lists unparsed until they are
+    /// accessed.
+    ///
+    /// Use this for large nested option blocks where most of the entries are
+    /// never read.
+    pub fn parse_lazy(p: ParseStream) -> Result<MetaValue, Error> {
+        Self::parse_with(p, true)
+    }
+
+    fn parse_with(p: ParseStream, lazy: bool) -> Result<MetaValue, Error> {
         if let Ok(name) = p.parse::<Path>() {
             // check if we have an eq or a paren in front
-            if p.peek(syn::token::Paren) {
+            if p.peek(syn::token::Paren) && lazy {
+                // this is a list, but we'll look at it later
+                let list;
+                let paren = syn::parenthesized!(list in p);
+                Ok(MetaValue::Lazy(LazyList::new(name, paren, list.parse()?)))
+            } else if p.peek(syn::token::Paren) {
                 // this is a list
                 let list;
                 Ok(MetaValue::List(
@@ -168,13 +432,24 @@ impl Parse for MetaValue {
                     MetaNameValue {
                         name,
                         eq: p.parse()?,
-                        value: p.parse()?,
+                        value: Box::new(p.call(parse_value)?),
                     }
                 ))
             } else {
                 // this is a path
                 Ok(MetaValue::Path(name))
             }
+        } else if p.peek(LitStr) && p.peek2(Token![=]) {
+            // this is a name-value pair with a string key
+            Ok(MetaValue::StrNameValue(
+                MetaStrNameValue {
+                    key: p.parse()?,
+                    eq: p.parse()?,
+                    value: Box::new(p.call(parse_value)?),
+                }
+            ))
+        } else if p.peek(syn::Lifetime) {
+            p.parse().map(MetaValue::Lifetime)
         } else {
             // parse literal
             p.parse::<Lit>().map(Into::into)
@@ -182,12 +457,102 @@ impl Parse for MetaValue {
     }
 }
 
+impl Parse for MetaValue {
+    fn parse(p: ParseStream) -> Result<MetaValue, Error> {
+        Self::parse_with(p, false)
+    }
+}
+
+/// Parses the value of a name-value pair.
+///
+/// This is either a literal, a lifetime, `none`, which [`Option`] takes as
+/// [`None`], or `env("VAR")`, which is replaced by the value of the
+/// environment variable at expansion time.
+fn parse_value(p: ParseStream) -> Result<MetaValue, Error> {
+    if p.peek(syn::Ident) && !p.peek2(syn::token::Paren) {
+        let none = p.parse::<syn::Ident>()?;
+
+        return match none == "none" {
+            true => Ok(MetaValue::Path(none.into())),
+            // most likely a string or lifetime missing its quotes or tick
+            false => Err(Error::new(
+                none.span(),
+                format!(
+                    "expected a literal, a lifetime, `none` or env(...), like `\"{0}\"` or `'{0}`",
+                    none,
+                ),
+            )),
+        };
+    }
+
+    if p.peek(syn::Lifetime) {
+        return p.parse().map(MetaValue::Lifetime);
+    }
+
+    if !(p.peek(syn::Ident) && p.peek2(syn::token::Paren)) {
+        return p.parse().map(MetaValue::Lit);
+    }
+
+    let func = p.parse::<syn::Ident>()?;
+    if func != "env" {
+        return Err(Error::new(func.span(), "expected a literal, a lifetime, `none` or env(...)"));
+    }
+
+    let args;
+    syn::parenthesized!(args in p);
+    let var = args.parse::<LitStr>()?;
+    args.parse::<Option<Token![,]>>()?;
+
+    if !args.is_empty() {
+        return Err(args.error("expected env(\"VAR\")"));
+    }
+
+    match std::env::var(var.value()) {
+        Ok(value) => Ok(MetaValue::Lit(Lit::Str(LitStr::new(&value, var.span())))),
+        Err(std::env::VarError::NotPresent) => Err(Error::new(
+            var.span(),
+            format!("environment variable `{}` is not set", var.value()),
+        )),
+        Err(std::env::VarError::NotUnicode(_)) => Err(Error::new(
+            var.span(),
+            format!("environment variable `{}` is not valid unicode", var.value()),
+        )),
+    }
+}
+
+impl ToTokens for MetaValue {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Self::Path(p) => p.to_tokens(tokens),
+            Self::NameValue(nv) => nv.to_tokens(tokens),
+            Self::List(list) => list.to_tokens(tokens),
+            Self::Lit(lit) => lit.to_tokens(tokens),
+            Self::Lazy(list) => list.to_tokens(tokens),
+            Self::Lifetime(lt) => lt.to_tokens(tokens),
+            Self::StrNameValue(nv) => nv.to_tokens(tokens),
+        }
+    }
+}
+
 /// A meta name-value pair.
 #[derive(Clone)]
 pub struct MetaNameValue {
     pub name: Path,
     pub eq: Token![=],
-    pub value: Lit,
+    /// This is always a [`MetaValue::Lit`] or [`MetaValue::Lifetime`] when
+    /// parsed, or the path `none`.
+    pub value: Box<MetaValue>,
+}
+
+/// A meta name-value pair with a string key, for keys that aren't
+/// identifiers.
+#[derive(Clone)]
+pub struct MetaStrNameValue {
+    pub key: LitStr,
+    pub eq: Token![=],
+    /// This is always a [`MetaValue::Lit`] or [`MetaValue::Lifetime`] when
+    /// parsed, or the path `none`.
+    pub value: Box<MetaValue>,
 }
 
 /// A meta list.
@@ -202,6 +567,111 @@ pub struct MetaList {
     pub list: Punctuated<MetaValue, Token![,]>,
 }
 
+impl ToTokens for MetaNameValue {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.name.to_tokens(tokens);
+        self.eq.to_tokens(tokens);
+        self.value.to_tokens(tokens);
+    }
+}
+
+impl ToTokens for MetaStrNameValue {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.key.to_tokens(tokens);
+        self.eq.to_tokens(tokens);
+        self.value.to_tokens(tokens);
+    }
+}
+
+impl ToTokens for MetaList {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match &self.name {
+            Some(name) => {
+                name.to_tokens(tokens);
+                self.paren.unwrap_or_default().surround(tokens, |tokens| {
+                    self.list.to_tokens(tokens);
+                });
+            }
+            // the root list has no parentheses
+            None => self.list.to_tokens(tokens),
+        }
+    }
+}
+
+/// A meta list that is parsed the first time it is accessed.
+///
+/// Its entries are parsed lazily too.
+#[derive(Clone)]
+pub struct LazyList {
+    pub name: Path,
+    pub paren: syn::token::Paren,
+    tokens: TokenStream,
+    parsed: OnceCell<Result<MetaList, Error>>,
+}
+
+impl LazyList {
+    pub fn new(name: Path, paren: syn::token::Paren, tokens: TokenStream) -> LazyList {
+        LazyList {
+            name,
+            paren,
+            tokens,
+            parsed: OnceCell::new(),
+        }
+    }
+
+    /// The unparsed tokens inside the parentheses.
+    pub fn tokens(&self) -> &TokenStream {
+        &self.tokens
+    }
+
+    /// Parses the list if it hasn't been parsed yet.
+    pub fn parsed(&self) -> &Result<MetaList, Error> {
+        self.parsed.get_or_init(|| {
+            let parser = |p: ParseStream| p.parse_terminated(MetaValue::parse_lazy);
+
+            Ok(MetaList {
+                name: Some(self.name.clone()),
+                paren: Some(self.paren),
+                list: parser.parse2(self.tokens.clone())?,
+            })
+        })
+    }
+
+    /// Parses the list if it hasn't been parsed yet, returning it.
+    pub fn list(&self) -> Result<&MetaList, Error> {
+        self.parsed().as_ref().map_err(Clone::clone)
+    }
+}
+
+impl ToTokens for LazyList {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.name.to_tokens(tokens);
+        self.paren.surround(tokens, |tokens| {
+            self.tokens.to_tokens(tokens);
+        });
+    }
+}
+
+/// The items of a [`MetaList`] by key, from [`MetaList::index`].
+pub struct KeyIndex<'a> {
+    entries: HashMap<String, &'a MetaValue>,
+}
+
+impl<'a> KeyIndex<'a> {
+    /// Gets a type by name, like [`MetaList::get`].
+    pub fn get<T>(&self, name: &str) -> Option<Result<T, Error>>
+    where T:
+        FromMeta,
+    {
+        self.find(name).map(|item| T::from_meta(item.value()))
+    }
+
+    /// Finds an item by name, like [`MetaList::find`].
+    pub fn find(&self, name: &str) -> Option<&'a MetaValue> {
+        self.entries.get(name).copied()
+    }
+}
+
 impl MetaList {
     /// Gets a type by name.
     ///
@@ -211,17 +681,57 @@ impl MetaList {
     where T:
         FromMeta,
     {
-        let item = self.list.iter()
-            .filter(|meta| meta.name().map(|n| n == name).unwrap_or(false))
-            .next()?;
+        // try to convert the type
+        self.find(name).map(|item| T::from_meta(item.value()))
+    }
 
-        let item = match item {
-            MetaValue::NameValue(nv) => MetaValue::Lit(nv.value.clone()),
-            item => item.clone(),
-        };
+    /// Gets the `n`th item and converts it, for positional arguments like
+    /// the ones in `#[version(1, 2, 3)]`.
+    pub fn get_index<T>(&self, n: usize) -> Option<Result<T, Error>>
+    where T:
+        FromMeta,
+    {
+        self.list.iter().nth(n).map(T::from_meta)
+    }
 
-        // try to convert the type
-        Some(T::from_meta(&item))
+    /// Gets the first item and converts it.
+    pub fn first<T>(&self) -> Option<Result<T, Error>>
+    where T:
+        FromMeta,
+    {
+        self.list.first().map(T::from_meta)
+    }
+
+    /// Gets the last item and converts it.
+    pub fn last<T>(&self) -> Option<Result<T, Error>>
+    where T:
+        FromMeta,
+    {
+        self.list.last().map(T::from_meta)
+    }
+
+    /// Finds an item by name, without converting it.
+    ///
+    /// This compares `name` to every key before it, which stringifies each
+    /// one, so looking up many keys of a long list is better done through
+    /// [`MetaList::index`].
+    pub fn find(&self, name: &str) -> Option<&MetaValue> {
+        self.list.iter()
+            .find(|meta| meta.has_name(name))
+    }
+
+    /// Indexes the items by key, stringifying each key once.
+    pub fn index(&self) -> KeyIndex<'_> {
+        let mut entries = HashMap::new();
+
+        for entry in self.list.iter() {
+            if let Some(key) = entry.key() {
+                // the first entry wins, like `MetaList::find`
+                entries.entry(key).or_insert(entry);
+            }
+        }
+
+        KeyIndex { entries }
     }
 
     pub fn parse_root_attr(p: ParseStream) -> Result<MetaList, Error> {
@@ -233,6 +743,116 @@ impl MetaList {
             }
         )
     }
+
+    /// Like [`MetaList::parse_root_attr`], but an entry that doesn't parse
+    /// doesn't stop the ones after it.
+    ///
+    /// A bad entry is skipped up to the next comma, and its error is put in
+    /// the returned [`Partial`], so one typo doesn't hide every other
+    /// problem in a long attribute.
+    pub fn parse_root_attr_recovering(p: ParseStream) -> Result<Partial<MetaList>, Error> {
+        let mut list = MetaList::default();
+        let mut errors = Vec::new();
+
+        let mut entry = TokenStream::new();
+        let mut tokens = p.parse::<TokenStream>()?.into_iter().peekable();
+
+        while let Some(tt) = tokens.next() {
+            let comma = match &tt {
+                TokenTree::Punct(punct) if punct.as_char() == ',' => punct.span(),
+                _ => {
+                    entry.extend(Some(tt));
+
+                    // the last entry doesn't need a comma
+                    if tokens.peek().is_some() {
+                        continue;
+                    }
+                    Span::call_site()
+                }
+            };
+
+            let tts = std::mem::take(&mut entry);
+
+            if tts.is_empty() {
+                errors.push(Error::new(comma, "expected an entry before this comma"));
+                continue;
+            }
+
+            match MetaValue::parse.parse2(tts) {
+                Ok(meta) => list.list.push(meta),
+                Err(err) => errors.push(err),
+            }
+        }
+
+        Ok(Partial { value: list, errors })
+    }
+
+    /// Like [`MetaList::parse_root_attr`], but nested lists are parsed when
+    /// they are accessed. See [`MetaValue::parse_lazy`].
+    pub fn parse_root_attr_lazy(p: ParseStream) -> Result<MetaList, Error> {
+        Ok(
+            MetaList {
+                name: None,
+                paren: None,
+                list: p.parse_terminated(MetaValue::parse_lazy)?,
+            }
+        )
+    }
+
+    /// Collects the arguments of every attribute named `name` into one root
+    /// list.
+    ///
+    /// Bare attributes (`#[name]`) contribute nothing, and if there are no
+    /// matching attributes the list is empty.
+    pub fn from_attributes(attrs: &[Attribute], name: &str) -> Result<MetaList, Error> {
+        MetaList::from_attributes_any(attrs, &[name])
+    }
+
+    /// Like [`MetaList::from_attributes`], but collects the attributes named
+    /// any of `names`, in the order they appear.
+    pub fn from_attributes_any(attrs: &[Attribute], names: &[&str]) -> Result<MetaList, Error> {
+        let mut root = MetaList::default();
+
+        let attrs = attrs.iter()
+            .filter(|attr| names.iter().any(|name| attr.path.is_ident(name)));
+
+        for attr in attrs {
+            if attr.tokens.is_empty() {
+                continue;
+            }
+
+            let list = attr.parse_args_with(MetaList::parse_root_attr)?;
+            root.list.extend(list.list);
+        }
+
+        Ok(root)
+    }
+}
+
+/// A value that was parsed as far as it could be, alongside the errors that
+/// stopped the rest.
+///
+/// `#[derive(FromMeta)]` types with `#[macrotk(builder)]` produce these from
+/// `from_meta_partial`, with every field that parsed set on the builder.
+/// Types with `#[macrotk(lenient)]` produce them from `from_meta_lenient`.
+pub struct Partial<T> {
+    pub value: T,
+    pub errors: Vec<Error>,
+}
+
+impl<T> Partial<T> {
+    /// Whether everything parsed.
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// The value, or the errors combined into one if there are any.
+    pub fn into_result(self) -> Result<T, Error> {
+        match crate::errors::combine(self.errors) {
+            Some(err) => Err(err),
+            None => Ok(self.value),
+        }
+    }
 }
 
 /// Helper type for parsing attribute token streams in an attribute proc
@@ -245,6 +865,23 @@ impl<T> Meta<T> {
     }
 }
 
+impl<T> Meta<T>
+where T:
+    FromMeta,
+{
+    /// Parses an attribute token stream, leaving nested lists unparsed until
+    /// they are accessed.
+    ///
+    /// Use this with [`Parser`](syn::parse::Parser), as in
+    /// `Meta::<T>::parse_lazy.parse(attr)`.
+    pub fn parse_lazy(p: ParseStream) -> Result<Meta<T>, Error> {
+        p.call(MetaList::parse_root_attr_lazy)
+            .map(Into::into)
+            .and_then(|meta| T::from_meta(&meta))
+            .map(Meta)
+    }
+}
+
 impl<T> std::ops::Deref for Meta<T> {
     type Target = T;
 
@@ -286,13 +923,248 @@ impl FromMeta for LitStr {
     }
 }
 
+impl ToMeta for LitStr {
+    fn to_meta(&self) -> Option<MetaValue> {
+        Some(MetaValue::Lit(Lit::Str(self.clone())))
+    }
+}
+
+impl FromMeta for bool {
+    /// A bare key, like `verbose`, is `true`.
+    fn from_meta(meta: &MetaValue) -> Result<bool, Error> {
+        match meta {
+            // `flag = none` parses as the path `none`, which isn't a bare key
+            MetaValue::Path(path) if path.is_ident("none") => {
+                Err(Error::new(path.span(), "`none` is only valid for Option fields"))
+            }
+            MetaValue::Path(_) => Ok(true),
+            MetaValue::Lit(Lit::Bool(lit)) => Ok(lit.value),
+            meta => Err(Error::new(meta.span(), "expected bool literal")),
+        }
+    }
+}
+
+impl ToMeta for bool {
+    fn to_meta(&self) -> Option<MetaValue> {
+        Some(MetaValue::Lit(Lit::Bool(syn::LitBool { value: *self, span: Span::call_site() })))
+    }
+}
+
+/// Checks that the suffix of a numeric literal, if it has one, is `ty`.
+fn check_suffix(suffix: &str, span: Span, ty: &str) -> Result<(), Error> {
+    match suffix {
+        "" => Ok(()),
+        suffix if suffix == ty => Ok(()),
+        suffix => Err(Error::new(
+            span,
+            format!("expected a `{}` literal, found a `{}` literal", ty, suffix),
+        )),
+    }
+}
+
+fn out_of_range(span: Span, ty: &str) -> Error {
+    Error::new(span, format!("value out of range for `{}`", ty))
+}
+
+macro_rules! int_impls {
+    ($($ty:ty => $unsuffixed:ident),*) => {$(
+        impl FromMeta for $ty {
+            fn from_meta(meta: &MetaValue) -> Result<$ty, Error> {
+                match meta.literal()? {
+                    Lit::Int(lit) => {
+                        check_suffix(lit.suffix(), lit.span(), stringify!($ty))?;
+
+                        lit.base10_parse().map_err(|_| out_of_range(lit.span(), stringify!($ty)))
+                    }
+                    lit => Err(Error::new(lit.span(), "expected integer literal")),
+                }
+            }
+        }
+
+        impl ToMeta for $ty {
+            fn to_meta(&self) -> Option<MetaValue> {
+                Some(MetaValue::Lit(Lit::new(proc_macro2::Literal::$unsuffixed(*self))))
+            }
+        }
+    )*};
+}
+
+int_impls!(
+    u8 => u8_unsuffixed, u16 => u16_unsuffixed, u32 => u32_unsuffixed,
+    u64 => u64_unsuffixed, u128 => u128_unsuffixed, usize => usize_unsuffixed,
+    i8 => i8_unsuffixed, i16 => i16_unsuffixed, i32 => i32_unsuffixed,
+    i64 => i64_unsuffixed, i128 => i128_unsuffixed, isize => isize_unsuffixed
+);
+
+macro_rules! float_impls {
+    ($($ty:ty => $unsuffixed:ident),*) => {$(
+        impl FromMeta for $ty {
+            fn from_meta(meta: &MetaValue) -> Result<$ty, Error> {
+                match meta.literal()? {
+                    Lit::Float(lit) => {
+                        check_suffix(lit.suffix(), lit.span(), stringify!($ty))?;
+                        lit.base10_parse()
+                    }
+                    Lit::Int(lit) => {
+                        check_suffix(lit.suffix(), lit.span(), stringify!($ty))?;
+                        lit.base10_parse()
+                    }
+                    lit => Err(Error::new(lit.span(), "expected float literal")),
+                }
+            }
+        }
+
+        impl ToMeta for $ty {
+            /// # Panics
+            /// Panics if the value is infinite or NaN, which have no literal.
+            fn to_meta(&self) -> Option<MetaValue> {
+                Some(MetaValue::Lit(Lit::new(proc_macro2::Literal::$unsuffixed(*self))))
+            }
+        }
+    )*};
+}
+
+float_impls!(f32 => f32_unsuffixed, f64 => f64_unsuffixed);
+
 // other impls
 impl<T> FromMeta for Option<T>
 where T:
     FromMeta,
 {
+    /// A missing key is [`None`] with `#[macrotk(default)]`, and so is
+    /// `key = none`.
     fn from_meta(p: &MetaValue) -> Result<Option<T>, Error> {
-        Ok(Some(T::from_meta(p)?))
+        match p {
+            MetaValue::Path(path) if path.is_ident("none") => Ok(None),
+            p => Ok(Some(T::from_meta(p)?)),
+        }
+    }
+}
+
+
+impl<T> FromMeta for Box<T>
+where T:
+    FromMeta,
+{
+    fn from_meta(p: &MetaValue) -> Result<Box<T>, Error> {
+        T::from_meta(p).map(Box::new)
+    }
+}
+
+impl<T> FromMeta for std::rc::Rc<T>
+where T:
+    FromMeta,
+{
+    fn from_meta(p: &MetaValue) -> Result<std::rc::Rc<T>, Error> {
+        T::from_meta(p).map(std::rc::Rc::new)
+    }
+}
+
+impl<T> FromMeta for std::sync::Arc<T>
+where T:
+    FromMeta,
+{
+    fn from_meta(p: &MetaValue) -> Result<std::sync::Arc<T>, Error> {
+        T::from_meta(p).map(std::sync::Arc::new)
     }
 }
 
+impl FromMeta for Lifetime {
+    /// A string literal like `"'de"` works too.
+    fn from_meta(meta: &MetaValue) -> Result<Lifetime, Error> {
+        match meta {
+            MetaValue::Lifetime(lt) => Ok(lt.clone()),
+            MetaValue::Lit(Lit::Str(s)) => s.parse::<Lifetime>().map_err(|_| missing_tick(s.span(), &s.value())),
+            MetaValue::Path(path) => match path.get_ident() {
+                Some(ident) => Err(missing_tick(ident.span(), &ident.to_string())),
+                None => Err(Error::new(path.span(), "expected a lifetime")),
+            },
+            meta => Err(Error::new(meta.span(), "expected a lifetime")),
+        }
+    }
+}
+
+fn missing_tick(span: Span, name: &str) -> Error {
+    match name.starts_with('\'') {
+        true => Error::new(span, "expected a lifetime"),
+        false => Error::new(span, format!("expected a lifetime; add a tick, like `'{}`", name)),
+    }
+}
+
+impl<T> FromMeta for Vec<T>
+where T:
+    FromMeta,
+{
+    /// Parses each item of a list, like `key(a, b, c)`.
+    fn from_meta(p: &MetaValue) -> Result<Vec<T>, Error> {
+        let mut items = Vec::new();
+        let mut errors = Vec::new();
+
+        for item in p.list()?.list.iter() {
+            match T::from_meta(item) {
+                Ok(item) => items.push(item),
+                Err(err) => errors.push(err),
+            }
+        }
+
+        match crate::errors::combine(errors) {
+            Some(err) => Err(err),
+            None => Ok(items),
+        }
+    }
+}
+
+impl<T> ToMeta for Option<T>
+where T:
+    ToMeta,
+{
+    fn to_meta(&self) -> Option<MetaValue> {
+        self.as_ref().and_then(T::to_meta)
+    }
+}
+
+impl ToMeta for Lifetime {
+    fn to_meta(&self) -> Option<MetaValue> {
+        Some(MetaValue::Lifetime(self.clone()))
+    }
+}
+
+impl<T> ToMeta for Vec<T>
+where T:
+    ToMeta,
+{
+    fn to_meta(&self) -> Option<MetaValue> {
+        Some(MetaValue::List(MetaList {
+            name: None,
+            paren: None,
+            list: self.iter().filter_map(T::to_meta).collect(),
+        }))
+    }
+}
+
+impl<T> ToMeta for Box<T>
+where T:
+    ToMeta,
+{
+    fn to_meta(&self) -> Option<MetaValue> {
+        T::to_meta(self)
+    }
+}
+
+impl<T> ToMeta for std::rc::Rc<T>
+where T:
+    ToMeta,
+{
+    fn to_meta(&self) -> Option<MetaValue> {
+        T::to_meta(self)
+    }
+}
+
+impl<T> ToMeta for std::sync::Arc<T>
+where T:
+    ToMeta,
+{
+    fn to_meta(&self) -> Option<MetaValue> {
+        T::to_meta(self)
+    }
+}
@@ -0,0 +1,31 @@
+use proc_macro_crate::FoundCrate;
+
+use syn::{Ident, Path};
+
+use proc_macro2::Span;
+
+/// Gets a path to a crate as the crate currently being compiled sees it.
+///
+/// This respects dependencies renamed in the consumer's `Cargo.toml`, and
+/// resolves to `crate` inside the crate itself. If the crate can't be found,
+/// this falls back to `::name`.
+pub fn crate_path(name: &str) -> Path {
+    let name = match proc_macro_crate::crate_name(name) {
+        // integration tests and examples also count as the crate itself, but
+        // they can only reach it by name
+        Ok(FoundCrate::Itself) if is_compiling(name) => {
+            return Ident::new("crate", Span::call_site()).into();
+        }
+        Ok(FoundCrate::Name(name)) => name,
+        _ => name.replace('-', "_"),
+    };
+
+    let ident = Ident::new(&name, Span::call_site());
+    syn::parse_quote!(::#ident)
+}
+
+fn is_compiling(name: &str) -> bool {
+    std::env::var("CARGO_CRATE_NAME")
+        .map(|krate| krate == name.replace('-', "_"))
+        .unwrap_or(false)
+}
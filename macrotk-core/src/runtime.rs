@@ -0,0 +1,654 @@
+//! Support code for `#[derive(FromMeta)]`.
+//!
+//! This is not public API. Derived impls call into this instead of inlining
+//! the lookups, which keeps their expansions small.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::RangeBounds;
+use std::slice;
+
+use syn::{Attribute, Error, Lit, LitInt, LitStr};
+use syn::spanned::Spanned;
+
+use quote::ToTokens;
+
+use proc_macro2::Span;
+
+use crate::meta::{FromMeta, MetaList, MetaValue};
+
+/// The fields of a derived struct, taken in declaration order.
+pub struct FieldTable<'a> {
+    // names are stringified once up front, since comparing an `Ident` to a
+    // `&str` allocates every time
+    entries: HashMap<String, &'a MetaValue>,
+    keys: &'static [&'static str],
+    names: slice::Iter<'static, &'static str>,
+    renames: &'static [(&'static str, &'static str)],
+    /// The entry of the field that was taken last, if it was present.
+    last: Option<&'a MetaValue>,
+    meta: &'a MetaValue,
+}
+
+impl<'a> FieldTable<'a> {
+    /// Creates a table over `meta`, which must be a list.
+    ///
+    /// `renames` maps old keys to the fields they were renamed to. Old keys
+    /// still work, but are deprecated. The deprecation is a pending
+    /// [warning](crate::warnings), which only reaches the user if the macro
+    /// renders it.
+    pub fn new(
+        meta: &'a MetaValue,
+        names: &'static [&'static str],
+        renames: &'static [(&'static str, &'static str)],
+    ) -> Result<FieldTable<'a>, Error> {
+        let mut entries = HashMap::new();
+
+        for entry in meta.list()?.list.iter() {
+            if let Some(mut key) = entry.key() {
+                if let Some((old, new)) = renames.iter().find(|(old, _)| key == *old) {
+                    crate::warnings::deprecated(
+                        key_span(entry),
+                        format!("`{}` is renamed to `{}`", old, new),
+                    );
+                    key = new.to_string();
+                }
+
+                // the first entry wins, like `MetaList::get`
+                entries.entry(key).or_insert(entry);
+            }
+        }
+
+        Ok(FieldTable {
+            entries,
+            keys: names,
+            names: names.iter(),
+            renames,
+            last: None,
+            meta,
+        })
+    }
+
+    /// Parses the next field, which must be present.
+    pub fn take<T>(&mut self) -> Result<T, Error>
+    where T:
+        FromMeta,
+    {
+        let (name, entry) = self.next_entry();
+
+        entry.map(parse).unwrap_or_else(|| Err(missing(name)))
+    }
+
+    /// Parses the next field, if it is present.
+    pub fn take_opt<T>(&mut self) -> Result<Option<T>, Error>
+    where T:
+        FromMeta,
+    {
+        let (_, entry) = self.next_entry();
+
+        entry.map(parse).transpose()
+    }
+
+    /// Parses the next field, if it is present, putting its error in
+    /// `errors` if it doesn't parse.
+    pub fn take_partial<T>(&mut self, errors: &mut Vec<Error>) -> Option<T>
+    where T:
+        FromMeta,
+    {
+        match self.take_opt() {
+            Ok(value) => value,
+            Err(err) => {
+                errors.push(err);
+                None
+            }
+        }
+    }
+
+    /// Parses the next field, falling back to `f` if it isn't present.
+    pub fn take_or_else<T, F>(&mut self, f: F) -> Result<T, Error>
+    where
+        T: FromMeta,
+        F: FnOnce() -> T,
+    {
+        let (_, entry) = self.next_entry();
+
+        entry.map(parse).unwrap_or_else(|| Ok(f()))
+    }
+
+    /// Parses the next field, falling back to `f` if it isn't present or
+    /// doesn't parse. Its error goes in `errors` if it doesn't parse.
+    pub fn take_lenient<T, F>(&mut self, errors: &mut Vec<Error>, f: F) -> T
+    where
+        T: FromMeta,
+        F: FnOnce() -> T,
+    {
+        let (_, entry) = self.next_entry();
+
+        match entry.map(parse).transpose() {
+            Ok(Some(value)) => value,
+            Ok(None) => f(),
+            Err(err) => {
+                errors.push(err);
+
+                // checks shouldn't look at the entry the default replaced
+                self.last = None;
+                f()
+            }
+        }
+    }
+
+    /// Checks that exactly one of `keys` is present.
+    pub fn check_exactly_one_of(&self, keys: &[&str]) -> Result<(), Error> {
+        let present = self.present(keys);
+
+        match present.len() {
+            1 => Ok(()),
+            0 => Err(Error::new(
+                self.meta.span(),
+                format!("expected one of {}", key_list(keys)),
+            )),
+            _ => Err(key_errors(&present, |_| {
+                format!("only one of {} can be given", key_list(keys))
+            })),
+        }
+    }
+
+    /// Checks that at least one of `keys` is present.
+    pub fn check_at_least_one_of(&self, keys: &[&str]) -> Result<(), Error> {
+        match self.present(keys).is_empty() {
+            false => Ok(()),
+            true => Err(Error::new(
+                self.meta.span(),
+                format!("expected at least one of {}", key_list(keys)),
+            )),
+        }
+    }
+
+    /// Checks that `key` and `other` aren't both present.
+    pub fn check_conflict(&self, key: &str, other: &str) -> Result<(), Error> {
+        match self.present(&[key, other])[..] {
+            [a, b] => Err(key_errors(&[a, b], |_| {
+                format!("`{}` and `{}` can't both be given", key, other)
+            })),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that `other` is present if `key` is.
+    pub fn check_requires(&self, key: &str, other: &str) -> Result<(), Error> {
+        match self.present(&[key, other])[..] {
+            [entry] if self.entries.contains_key(key) => Err(key_errors(&[entry], |_| {
+                format!("`{}` requires `{}`", key, other)
+            })),
+            _ => Ok(()),
+        }
+    }
+
+    /// An error for each entry that isn't a field or an old name of one.
+    pub fn unknown_keys(&self) -> Vec<Error> {
+        let list = match self.meta.list() {
+            Ok(list) => list,
+            Err(_) => return Vec::new(),
+        };
+
+        list.list.iter()
+            .filter_map(|entry| match entry.key() {
+                Some(key) if self.is_key(&key) => None,
+                Some(key) => Some(Error::new(key_span(entry), format!("unknown key `{}`", key))),
+                None => Some(Error::new(entry.span(), "expected a key")),
+            })
+            .collect()
+    }
+
+    /// Checks that every entry is a field or an old name of one.
+    pub fn deny_unknown(&self) -> Result<(), Error> {
+        match crate::errors::combine(self.unknown_keys()) {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    fn is_key(&self, name: &str) -> bool {
+        self.keys.contains(&name) || self.renames.iter().any(|(old, _)| *old == name)
+    }
+
+    /// The entries of `keys` that are present, in the order of `keys`.
+    fn present(&self, keys: &[&str]) -> Vec<&'a MetaValue> {
+        keys.iter()
+            .filter_map(|key| self.entries.get(*key).copied())
+            .collect()
+    }
+
+    /// Checks that the field that was just taken is in `range`, which is
+    /// written as `text`.
+    ///
+    /// Defaults aren't checked.
+    pub fn check_range<T, R>(&self, value: &T, range: R, text: &str) -> Result<(), Error>
+    where
+        T: PartialOrd,
+        R: RangeBounds<T>,
+    {
+        match self.last {
+            Some(entry) if !range.contains(value) => Err(Error::new(
+                entry.value().span(),
+                format!("expected a value in the range {}", text),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that the field that was just taken is a string literal
+    /// matching `pattern`.
+    ///
+    /// Defaults aren't checked.
+    #[cfg(feature = "regex")]
+    pub fn check_matches(&self, pattern: &str) -> Result<(), Error> {
+        let entry = match self.last {
+            Some(entry) => entry.value(),
+            None => return Ok(()),
+        };
+
+        let matches = match entry.literal()? {
+            Lit::Str(s) => regex::Regex::new(pattern)
+                .map(|re| re.is_match(&s.value()))
+                .map_err(|err| Error::new(s.span(), err))?,
+            lit => return Err(Error::new(lit.span(), "expected str literal")),
+        };
+
+        match matches {
+            true => Ok(()),
+            false => Err(Error::new(
+                entry.span(),
+                format!("expected a value matching `{}`", pattern),
+            )),
+        }
+    }
+
+    /// Checks that the list that was just taken has at least `min` items.
+    ///
+    /// Defaults aren't checked.
+    pub fn check_min_items(&self, min: usize) -> Result<(), Error> {
+        match self.last_list()? {
+            Some(list) if list.list.len() < min => Err(Error::new(
+                list_span(list),
+                format!("expected at least {} item{}", min, plural(min)),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that the list that was just taken has at most `max` items.
+    ///
+    /// Defaults aren't checked.
+    pub fn check_max_items(&self, max: usize) -> Result<(), Error> {
+        let list = match self.last_list()? {
+            Some(list) => list,
+            None => return Ok(()),
+        };
+
+        match list.list.iter().nth(max) {
+            Some(item) => Err(Error::new(
+                item.span(),
+                format!("expected at most {} item{}", max, plural(max)),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that the items of the list that was just taken are unique.
+    ///
+    /// Items are compared by their tokens. Defaults aren't checked.
+    pub fn check_unique(&self) -> Result<(), Error> {
+        let list = match self.last_list()? {
+            Some(list) => list,
+            None => return Ok(()),
+        };
+
+        let mut seen = std::collections::HashSet::new();
+        let duplicates = list.list.iter()
+            .filter(|item| !seen.insert(item.to_token_stream().to_string()))
+            .map(|item| Error::new(item.span(), "duplicate item"));
+
+        match crate::errors::combine(duplicates) {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    fn last_list(&self) -> Result<Option<&'a MetaList>, Error> {
+        self.last.map(|entry| entry.value().list()).transpose()
+    }
+
+    fn next_entry(&mut self) -> (&'static str, Option<&'a MetaValue>) {
+        let name = *self.names.next().expect("more fields parsed than in the table");
+
+        self.last = self.entries.get(name).copied();
+        (name, self.last)
+    }
+}
+
+/// The fields of a builder that are set, for checking the same relations
+/// between keys as [`FieldTable`].
+///
+/// A builder doesn't remember where its fields came from, so errors point at
+/// the call site.
+pub struct Present {
+    keys: Vec<&'static str>,
+}
+
+impl Present {
+    /// `fields` pairs each key with whether its field is set.
+    pub fn new(fields: &[(&'static str, bool)]) -> Present {
+        Present {
+            keys: fields.iter()
+                .filter(|(_, set)| *set)
+                .map(|(key, _)| *key)
+                .collect(),
+        }
+    }
+
+    /// Like [`FieldTable::check_exactly_one_of`].
+    pub fn check_exactly_one_of(&self, keys: &[&str]) -> Result<(), Error> {
+        match self.count(keys) {
+            1 => Ok(()),
+            0 => Err(Error::new(Span::call_site(), format!("expected one of {}", key_list(keys)))),
+            _ => Err(Error::new(
+                Span::call_site(),
+                format!("only one of {} can be given", key_list(keys)),
+            )),
+        }
+    }
+
+    /// Like [`FieldTable::check_at_least_one_of`].
+    pub fn check_at_least_one_of(&self, keys: &[&str]) -> Result<(), Error> {
+        match self.count(keys) {
+            0 => Err(Error::new(
+                Span::call_site(),
+                format!("expected at least one of {}", key_list(keys)),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Like [`FieldTable::check_conflict`].
+    pub fn check_conflict(&self, key: &str, other: &str) -> Result<(), Error> {
+        match self.count(&[key, other]) {
+            2 => Err(Error::new(
+                Span::call_site(),
+                format!("`{}` and `{}` can't both be given", key, other),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Like [`FieldTable::check_requires`].
+    pub fn check_requires(&self, key: &str, other: &str) -> Result<(), Error> {
+        match self.count(&[key]) == 1 && self.count(&[other]) == 0 {
+            true => Err(Error::new(Span::call_site(), format!("`{}` requires `{}`", key, other))),
+            false => Ok(()),
+        }
+    }
+
+    fn count(&self, keys: &[&str]) -> usize {
+        keys.iter().filter(|key| self.keys.contains(key)).count()
+    }
+}
+
+/// The span of the key of an entry, or the whole entry if it has none.
+fn key_span(entry: &MetaValue) -> Span {
+    entry.key_span().unwrap_or_else(|| entry.span())
+}
+
+/// The span of the brackets of a list, or the whole list if it has none.
+fn list_span(list: &MetaList) -> Span {
+    list.paren.map(|paren| paren.span).unwrap_or_else(|| list.span())
+}
+
+fn plural(n: usize) -> &'static str {
+    if n == 1 { "" } else { "s" }
+}
+
+/// Renders keys like `` `a`, `b`, `c` ``.
+fn key_list(keys: &[&str]) -> String {
+    keys.iter()
+        .map(|key| format!("`{}`", key))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// An error on the name of each entry, combined into one.
+fn key_errors<F>(entries: &[&MetaValue], mut msg: F) -> Error
+where F:
+    FnMut(&MetaValue) -> String,
+{
+    let errors = entries.iter()
+        .map(|entry| {
+            Error::new(key_span(entry), msg(entry))
+        });
+
+    crate::errors::combine(errors).expect("no entries to report")
+}
+
+fn parse<T>(entry: &MetaValue) -> Result<T, Error>
+where T:
+    FromMeta,
+{
+    T::from_meta(entry.value())
+}
+
+/// Unwraps a field of a builder, which must have been set.
+pub fn require<T>(value: Option<T>, name: &str) -> Result<T, Error> {
+    value.ok_or_else(|| missing(name))
+}
+
+fn missing(name: &str) -> Error {
+    Error::new(Span::call_site(), format!("missing value for {}", name))
+}
+
+/// Takes the only entry of `meta`, which selects a variant of a derived enum.
+///
+/// Returns the entry alongside its name. A string literal selects a variant
+/// by its value, and is returned as the entry.
+pub fn variant(meta: &MetaValue) -> Result<(String, &MetaValue), Error> {
+    if let MetaValue::Lit(Lit::Str(s)) = meta {
+        return Ok((s.value(), meta));
+    }
+
+    let list = meta.list()?;
+
+    let entry = match list.list.first() {
+        Some(entry) if list.list.len() == 1 => entry,
+        _ => return Err(Error::new(list.span(), "expected exactly one variant")),
+    };
+
+    match entry.name() {
+        Some(name) => Ok((name.to_string(), entry)),
+        None => Err(Error::new(entry.span(), "expected a variant name")),
+    }
+}
+
+/// Checks that the entry of a unit variant has nothing after its name.
+pub fn unit(entry: &MetaValue) -> Result<(), Error> {
+    match entry {
+        MetaValue::Lit(_) => Ok(()),
+        entry => entry.path().map(|_| ()),
+    }
+}
+
+/// Takes `meta` as an integer literal, if it is one, to be matched against
+/// the discriminants of a fieldless enum.
+pub fn discriminant(meta: &MetaValue) -> Result<Option<(i128, &LitInt)>, Error> {
+    match meta {
+        MetaValue::Lit(Lit::Int(i)) => i.base10_parse()
+            .map(|d| Some((d, i)))
+            .map_err(|_| Error::new(i.span(), "discriminant out of range")),
+        _ => Ok(None),
+    }
+}
+
+/// The error for an integer literal that isn't the discriminant of any
+/// variant.
+pub fn unknown_discriminant(lit: &LitInt) -> Error {
+    Error::new(
+        lit.span(),
+        format!("no variant has the discriminant {}", lit.base10_digits()),
+    )
+}
+
+/// Checks that `meta` is the unit variant `name` of an untagged enum.
+///
+/// This is either the bare path `name`, or a list with only that path in it.
+pub fn unit_variant(meta: &MetaValue, name: &str) -> Result<(), Error> {
+    let path = match meta {
+        MetaValue::Path(path) => path,
+        meta => variant(meta)?.1.path()?,
+    };
+
+    if path.is_ident(name) {
+        Ok(())
+    } else {
+        Err(Error::new(path.span(), format!("expected `{}`", name)))
+    }
+}
+
+/// The error for a variant name that isn't in `expected`.
+pub fn unknown_variant(entry: &MetaValue, name: &str, expected: &[&str]) -> Error {
+    Error::new(
+        key_span(entry),
+        format!("unknown variant `{}`, expected one of: {}", name, expected.join(", ")),
+    )
+}
+
+/// The error for an untagged enum where no variant matched.
+///
+/// The error of each variant is included.
+pub fn no_variant_matched(meta: &MetaValue, errors: Vec<Error>) -> Error {
+    let mut err = Error::new(meta.span(), "value did not match any variant");
+
+    if let Some(variants) = crate::errors::combine(errors) {
+        err.combine(variants);
+    }
+
+    err
+}
+
+/// Collects the entries that are present into a root list.
+pub fn root<I>(entries: I) -> MetaValue
+where I:
+    IntoIterator<Item = Option<MetaValue>>,
+{
+    MetaValue::List(MetaList {
+        name: None,
+        paren: None,
+        list: entries.into_iter().flatten().collect(),
+    })
+}
+
+/// A bare path, like a unit variant.
+pub fn path(name: &str) -> MetaValue {
+    MetaValue::Path(syn::Ident::new(name, Span::call_site()).into())
+}
+
+/// Appends the arguments of the foreign attributes named `name` to `list`,
+/// as the value of `key`.
+///
+/// A lone literal, like the one in `#[doc = "..."]`, becomes `key = "..."`.
+pub fn push_foreign(
+    list: &mut MetaList,
+    attrs: &[Attribute],
+    key: &str,
+    name: &str,
+) -> Result<(), Error> {
+    let mut entries = MetaList::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident(name)) {
+        match MetaValue::from(attr.parse_meta()?) {
+            MetaValue::List(foreign) => entries.list.extend(foreign.list),
+            MetaValue::NameValue(nv) => entries.list.push(*nv.value),
+            _ => (),
+        }
+    }
+
+    let value = match entries.list.len() {
+        0 => return Ok(()),
+        1 if matches!(entries.list[0], MetaValue::Lit(_)) => entries.list.pop().unwrap().into_value(),
+        _ => MetaValue::List(entries),
+    };
+
+    list.list.push(MetaValue::entry(key, value));
+    Ok(())
+}
+
+/// Appends the options in the config file `file_name` to `meta`, if it is a
+/// root list.
+///
+/// The first entry for a key wins, so the attribute overrides the file.
+#[cfg(feature = "toml")]
+pub fn with_config<'a>(meta: &'a MetaValue, file_name: &str) -> Result<Cow<'a, MetaValue>, Error> {
+    let list = meta.list()?;
+
+    if list.name.is_some() {
+        return Ok(Cow::Borrowed(meta));
+    }
+
+    match crate::config::load(file_name)? {
+        Some(config) => {
+            let mut list = list.clone();
+            list.list.extend(config.list);
+
+            Ok(Cow::Owned(MetaValue::List(list)))
+        }
+        None => Ok(Cow::Borrowed(meta)),
+    }
+}
+
+/// Expands the word form of `meta`, a list with only a value in it, like
+/// `table("users")` or `table(users)`, into the value of `key`.
+///
+/// A bare identifier becomes a string literal, unless it is one of `keys`.
+pub fn word<'a>(meta: &'a MetaValue, key: &str, keys: &[&str]) -> Result<Cow<'a, MetaValue>, Error> {
+    let list = meta.list()?;
+
+    let value = match list.list.first() {
+        Some(entry) if list.list.len() == 1 => match entry {
+            MetaValue::Lit(_) | MetaValue::Lifetime(_) => entry.clone(),
+            MetaValue::Path(path) => match path.get_ident() {
+                Some(ident) if !keys.iter().any(|key| ident == key) => {
+                    MetaValue::Lit(Lit::Str(LitStr::new(&ident.to_string(), ident.span())))
+                }
+                _ => return Ok(Cow::Borrowed(meta)),
+            },
+            _ => return Ok(Cow::Borrowed(meta)),
+        },
+        _ => return Ok(Cow::Borrowed(meta)),
+    };
+
+    Ok(Cow::Owned(MetaValue::List(MetaList {
+        name: list.name.clone(),
+        paren: list.paren,
+        list: std::iter::once(MetaValue::entry(key, value)).collect(),
+    })))
+}
+
+/// Parses the arguments of an attribute, like the `a = 1, b` of
+/// `#[attr(a = 1, b)]`, into the fields of a `parse_meta!`.
+pub fn parse_meta<T, F>(
+    tokens: proc_macro2::TokenStream,
+    names: &'static [&'static str],
+    f: F,
+) -> Result<T, Error>
+where F:
+    FnOnce(&mut FieldTable) -> Result<T, Error>,
+{
+    use syn::parse::Parser as _;
+
+    let meta = MetaValue::List(MetaList::parse_root_attr.parse2(tokens)?);
+
+    let mut table = FieldTable::new(&meta, names, &[])?;
+    let value = f(&mut table)?;
+
+    // unknown keys are errors, like in derived impls
+    table.deny_unknown()?;
+
+    Ok(value)
+}
@@ -0,0 +1,27 @@
+//! Reflection over the options a derived type accepts.
+
+/// Types that can list the keys they accept.
+///
+/// `#[derive(FromMeta)]` implements this.
+pub trait MetaSchema {
+    /// The keys this accepts, in declaration order.
+    fn fields() -> &'static [FieldSpec];
+}
+
+/// A key a [`MetaSchema`] type accepts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldSpec {
+    pub name: &'static str,
+    /// Whether the key must be present.
+    pub required: bool,
+    pub kind: FieldKind,
+}
+
+/// What a key of a [`MetaSchema`] type stands for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldKind {
+    /// A field, with its type as written.
+    Field(&'static str),
+    /// A variant of an enum, selected by name.
+    Variant,
+}
@@ -0,0 +1,188 @@
+//! Helpers for injecting fields into structs.
+
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Error, Field, Fields, FieldsNamed, FieldsUnnamed, Index, ItemStruct, Member};
+
+/// Records the fields injected into a struct.
+///
+/// Injecting through the same [`Injected`] keeps tuple indices up to date
+/// when fields are prepended.
+#[derive(Clone, Default)]
+pub struct Injected {
+    members: Vec<Member>,
+}
+
+impl Injected {
+    pub fn new() -> Injected {
+        Injected::default()
+    }
+
+    /// Appends a field to the end of a struct, returning its member.
+    ///
+    /// Named fields can only be injected into named or unit structs, and
+    /// unnamed fields into tuple or unit structs.
+    pub fn append(&mut self, item: &mut ItemStruct, field: Field) -> Result<Member, Error> {
+        let fields = fields_for(item, &field)?;
+        let member = member(&field, fields.len());
+
+        fields.push(field);
+        self.members.push(member.clone());
+
+        Ok(member)
+    }
+
+    /// Prepends a field to the start of a struct, returning its member.
+    ///
+    /// For tuple structs, this shifts the index of every existing field.
+    pub fn prepend(&mut self, item: &mut ItemStruct, field: Field) -> Result<Member, Error> {
+        let fields = fields_for(item, &field)?;
+        let member = member(&field, 0);
+
+        fields.insert(0, field);
+
+        if let Member::Unnamed(_) = member {
+            for member in self.members.iter_mut() {
+                if let Member::Unnamed(index) = member {
+                    index.index += 1;
+                }
+            }
+        }
+        self.members.push(member.clone());
+
+        Ok(member)
+    }
+
+    /// The members of every injected field, in injection order.
+    pub fn members(&self) -> &[Member] {
+        &self.members
+    }
+
+    /// Checks if a member refers to an injected field.
+    pub fn contains(&self, member: &Member) -> bool {
+        self.members.contains(member)
+    }
+}
+
+fn fields_for<'a>(
+    item: &'a mut ItemStruct,
+    field: &Field,
+) -> Result<&'a mut Punctuated<Field, syn::Token![,]>, Error> {
+    if let Fields::Unit = item.fields {
+        let span = item.ident.span();
+
+        if field.ident.is_some() {
+            // named structs don't end in a semicolon
+            item.semi_token = None;
+            item.fields = Fields::Named(FieldsNamed {
+                brace_token: syn::token::Brace(span),
+                named: Punctuated::new(),
+            });
+        } else {
+            item.fields = Fields::Unnamed(FieldsUnnamed {
+                paren_token: syn::token::Paren(span),
+                unnamed: Punctuated::new(),
+            });
+            item.semi_token.get_or_insert_with(|| syn::Token![;](span));
+        }
+    }
+
+    match (&mut item.fields, field.ident.is_some()) {
+        (Fields::Named(fields), true) => Ok(&mut fields.named),
+        (Fields::Unnamed(fields), false) => Ok(&mut fields.unnamed),
+        (_, true) => Err(Error::new(
+            field.span(),
+            "cannot inject a named field into a tuple struct",
+        )),
+        (_, false) => Err(Error::new(
+            field.span(),
+            "cannot inject an unnamed field into a struct with named fields",
+        )),
+    }
+}
+
+fn member(field: &Field, index: usize) -> Member {
+    match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index {
+            index: index as u32,
+            span: field.ty.span(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use syn::parse::Parser;
+
+    use quote::{quote, ToTokens};
+
+    fn named(tokens: proc_macro2::TokenStream) -> Field {
+        Field::parse_named.parse2(tokens).unwrap()
+    }
+
+    fn unnamed(tokens: proc_macro2::TokenStream) -> Field {
+        Field::parse_unnamed.parse2(tokens).unwrap()
+    }
+
+    fn assert_tokens(item: &ItemStruct, expected: proc_macro2::TokenStream) {
+        assert_eq!(item.to_token_stream().to_string(), expected.to_string());
+    }
+
+    #[test]
+    pub fn inject_named() {
+        let mut item: ItemStruct = syn::parse_quote!(struct Opts { name: String });
+        let mut injected = Injected::new();
+
+        let last = injected.append(&mut item, named(quote!(__last: u32))).unwrap();
+        let first = injected.prepend(&mut item, named(quote!(__first: u32))).unwrap();
+
+        assert_tokens(&item, quote!(struct Opts { __first: u32, name: String, __last: u32 }));
+        assert_eq!(injected.members(), [last, first]);
+        assert!(injected.contains(&syn::parse_quote!(__last)));
+        assert!(!injected.contains(&syn::parse_quote!(name)));
+    }
+
+    #[test]
+    pub fn inject_tuple() {
+        let mut item: ItemStruct = syn::parse_quote!(struct Pair(u8, u8););
+        let mut injected = Injected::new();
+
+        let last = injected.append(&mut item, unnamed(quote!(u32))).unwrap();
+        assert_eq!(last, syn::parse_quote!(2));
+
+        // prepending shifts the fields injected before
+        let first = injected.prepend(&mut item, unnamed(quote!(u64))).unwrap();
+        assert_eq!(first, syn::parse_quote!(0));
+
+        assert_tokens(&item, quote!(struct Pair(u64, u8, u8, u32);));
+        assert_eq!(injected.members(), [syn::parse_quote!(3), first]);
+    }
+
+    #[test]
+    pub fn inject_unit() {
+        let mut item: ItemStruct = syn::parse_quote!(struct Marker;);
+        Injected::new().append(&mut item, named(quote!(x: u32))).unwrap();
+        assert_tokens(&item, quote!(struct Marker { x: u32 }));
+
+        let mut item: ItemStruct = syn::parse_quote!(struct Marker;);
+        Injected::new().append(&mut item, unnamed(quote!(u32))).unwrap();
+        assert_tokens(&item, quote!(struct Marker(u32);));
+    }
+
+    #[test]
+    pub fn inject_mismatched() {
+        let mut item: ItemStruct = syn::parse_quote!(struct Pair(u8, u8););
+        let err = Injected::new().append(&mut item, named(quote!(x: u32))).unwrap_err();
+        assert_eq!(err.to_string(), "cannot inject a named field into a tuple struct");
+
+        let mut item: ItemStruct = syn::parse_quote!(struct Opts { name: String });
+        let err = Injected::new().prepend(&mut item, unnamed(quote!(u32))).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot inject an unnamed field into a struct with named fields",
+        );
+    }
+}
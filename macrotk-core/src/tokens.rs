@@ -0,0 +1,142 @@
+//! Helpers for building token streams.
+
+use quote::ToTokens;
+
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
+
+/// Tokens that expand to nothing when absent.
+#[derive(Clone, Debug, Default)]
+pub struct OptionTokens<T>(pub Option<T>);
+
+impl<T> ToTokens for OptionTokens<T>
+where T:
+    ToTokens,
+{
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        if let Some(t) = &self.0 {
+            t.to_tokens(tokens);
+        }
+    }
+}
+
+impl<T> From<Option<T>> for OptionTokens<T> {
+    fn from(opt: Option<T>) -> OptionTokens<T> {
+        OptionTokens(opt)
+    }
+}
+
+/// Converts an optional value into tokens, or an empty stream if it is
+/// `None`.
+pub fn some_or_empty<T>(opt: Option<T>) -> TokenStream
+where T:
+    ToTokens,
+{
+    OptionTokens(opt).into_token_stream()
+}
+
+/// Returns `tokens` if `cond` is true, or an empty stream otherwise.
+pub fn tokens_if<T>(cond: bool, tokens: T) -> TokenStream
+where T:
+    ToTokens,
+{
+    if cond {
+        tokens.into_token_stream()
+    } else {
+        TokenStream::new()
+    }
+}
+
+/// Sets the span of every token, including the tokens inside groups.
+///
+/// Use this to make errors in generated code point at the user's input.
+pub fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens.into_iter()
+        .map(|tt| respan_tree(tt, span, true))
+        .collect()
+}
+
+/// Sets the span of every top-level token, leaving the contents of groups
+/// untouched.
+pub fn respan_shallow(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens.into_iter()
+        .map(|tt| respan_tree(tt, span, false))
+        .collect()
+}
+
+fn respan_tree(tt: TokenTree, span: Span, deep: bool) -> TokenTree {
+    match tt {
+        TokenTree::Group(group) => {
+            let stream = if deep {
+                respan(group.stream(), span)
+            } else {
+                group.stream()
+            };
+
+            let mut new = Group::new(group.delimiter(), stream);
+            new.set_span(span);
+            new.into()
+        }
+        mut tt => {
+            tt.set_span(span);
+            tt
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use quote::quote;
+
+    #[test]
+    pub fn option_tokens() {
+        let some = OptionTokens::from(Some(quote!(a + b)));
+        let none = OptionTokens::<TokenStream>::from(None);
+
+        assert_eq!(quote!(#some).to_string(), quote!(a + b).to_string());
+        assert!(quote!(#none).is_empty());
+    }
+
+    #[test]
+    pub fn some_or_empty_tokens() {
+        assert_eq!(some_or_empty(Some(quote!(x))).to_string(), quote!(x).to_string());
+        assert!(some_or_empty(None::<TokenStream>).is_empty());
+    }
+
+    #[test]
+    pub fn tokens_if_cond() {
+        assert_eq!(tokens_if(true, quote!(pub)).to_string(), quote!(pub).to_string());
+        assert!(tokens_if(false, quote!(pub)).is_empty());
+    }
+
+    /// Every span in a stream, including groups' own delimiter spans.
+    fn spans(tokens: TokenStream, out: &mut Vec<Span>) {
+        for tt in tokens {
+            out.push(tt.span());
+            if let TokenTree::Group(group) = tt {
+                spans(group.stream(), out);
+            }
+        }
+    }
+
+    #[test]
+    pub fn respan_nested() {
+        let tokens: TokenStream = "a (b [c {d}])".parse().unwrap();
+        // the span of `a`
+        let span = tokens.clone().into_iter().next().unwrap().span();
+
+        let mut deep = Vec::new();
+        spans(respan(tokens.clone(), span), &mut deep);
+        assert_eq!(deep.len(), 7);
+        assert!(deep.iter().all(|s| s.start() == span.start() && s.end() == span.end()));
+
+        // shallow respanning leaves `b` and everything after it alone
+        let mut shallow = Vec::new();
+        spans(respan_shallow(tokens, span), &mut shallow);
+        let moved = shallow.iter()
+            .filter(|s| s.start() == span.start() && s.end() == span.end())
+            .count();
+        assert_eq!(moved, 2);
+    }
+}
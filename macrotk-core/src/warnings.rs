@@ -0,0 +1,53 @@
+//! Warnings, like deprecated keys.
+//!
+//! Proc macros can't emit warnings on stable, so they are rendered as uses of
+//! `#[deprecated]` items instead, which the compiler warns about at the span
+//! of the warning.
+//!
+//! Warnings are collected while parsing and rendered by the wrappers in
+//! [`entry`](crate::entry). A macro that parses without those wrappers, like
+//! one that calls `FromMeta` or `parse_meta!` from its own entry point, has
+//! to append [`take`] to its output itself, or the warnings are lost. They
+//! also pile up on the thread until something takes them.
+
+use std::cell::RefCell;
+
+use syn::LitStr;
+
+use quote::quote;
+
+use proc_macro2::{Ident, Span, TokenStream};
+
+thread_local! {
+    static PENDING: RefCell<Vec<(Span, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Warns at `span` that something is deprecated.
+pub fn deprecated<T>(span: Span, note: T)
+where T:
+    Into<String>,
+{
+    PENDING.with(|pending| pending.borrow_mut().push((span, note.into())));
+}
+
+/// Renders the pending warnings as items, clearing them.
+pub fn take() -> TokenStream {
+    let pending = PENDING.with(|pending| pending.replace(Vec::new()));
+
+    pending.into_iter()
+        .map(|(span, note)| {
+            let ident = Ident::new("__macrotk_deprecated", span);
+            let note = LitStr::new(&note, span);
+
+            quote! {
+                const _: () = {
+                    #[deprecated(note = #note)]
+                    #[allow(non_camel_case_types)]
+                    struct #ident;
+
+                    let _ = #ident;
+                };
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,210 @@
+//! Builders for derived structs, for assembling options from several
+//! sources.
+
+use syn::{Error, Fields, Generics, Ident, Path, Visibility};
+
+use quote::quote;
+
+use proc_macro2::TokenStream;
+
+use macrotk_core::impls::ImplBuilder;
+
+use crate::{checks, config_expr, field_table, named_fields, relations, word_expr, Container};
+
+/// Generates `{type_name}Builder`, which has every field of `type_name` as
+/// an `Option`.
+pub fn builder(
+    krate: &Path,
+    container: &Container,
+    vis: &Visibility,
+    type_name: &Ident,
+    generics: &Generics,
+    fields: &Fields,
+) -> Result<TokenStream, Error> {
+    let fields = named_fields(fields)?;
+    let builder_name = macrotk_core::ident::suffixed(type_name, "Builder");
+    let (_, ty_generics, where_clause) = generics.split_for_impl();
+
+    let idents = fields.iter().map(|field| &field.ident).collect::<Vec<_>>();
+    let tys = fields.iter().map(|field| &field.ty);
+    let table = field_table(krate, &fields);
+
+    let checks = fields.iter().map(checks).collect::<Vec<_>>();
+    let relations = relations(container, &fields)?;
+    let keys = fields.iter().map(|field| field.key()).collect::<Vec<_>>();
+
+    // the word form is just another spelling, so every source can use it,
+    // but the config file is only layered under a whole parse
+    let word = word_expr(krate, container, &fields)?;
+    let (merge_word, partial_word) = match &word {
+        Some(word) => (
+            Some(quote! {
+                let __w = #word?;
+                let __m: &#krate::meta::MetaValue = &__w;
+            }),
+            Some(quote! {
+                let __w = match #word {
+                    Ok(__w) => __w,
+                    Err(__err) => return ::std::vec![__err],
+                };
+                let __m: &#krate::meta::MetaValue = &__w;
+            }),
+        ),
+        None => (None, None),
+    };
+    let config = config_expr(krate, container).map(|config| quote! {
+        let __c = match #config {
+            Ok(__c) => __c,
+            Err(__err) => {
+                return #krate::meta::Partial {
+                    value: Self::builder(),
+                    errors: ::std::vec![__err],
+                };
+            }
+        };
+        let __m: &#krate::meta::MetaValue = &__c;
+    });
+
+    let unwrapper = fields.iter()
+        .map(|field| {
+            let ident = &field.ident;
+            let name = field.key();
+
+            if field.use_default {
+                quote!(#ident: self.#ident.unwrap_or_default(),)
+            } else {
+                quote!(#ident: #krate::runtime::require(self.#ident, #name)?,)
+            }
+        });
+
+    let doc = format!("A builder for [`{}`].", type_name);
+    let params = &generics.params;
+
+    let methods = ImplBuilder::inherent(&builder_name)
+        .with_generics(generics)
+        .bound_params(&syn::parse_quote!(#krate::meta::FromMeta))
+        .method(quote! {
+            /// Sets the fields that are present in `meta`, replacing any
+            /// that were set before.
+            pub fn merge(
+                &mut self,
+                __m: &#krate::meta::MetaValue,
+            ) -> ::std::result::Result<(), #krate::syn::Error> {
+                #merge_word
+                let mut __f = #table?;
+
+                #(
+                    if let Some(__v) = __f.take_opt()? {
+                        #checks
+                        self.#idents = Some(__v);
+                    }
+                )*
+
+                __f.deny_unknown()?;
+
+                Ok(())
+            }
+        })
+        .method(quote! {
+            /// Like [`merge`](Self::merge), but sets every field that parses,
+            /// returning the errors of the rest.
+            pub fn merge_partial(
+                &mut self,
+                __m: &#krate::meta::MetaValue,
+            ) -> ::std::vec::Vec<#krate::syn::Error> {
+                let mut __errors = ::std::vec::Vec::new();
+                #partial_word
+
+                let mut __f = match #table {
+                    Ok(__f) => __f,
+                    Err(__err) => return ::std::vec![__err],
+                };
+
+                #(
+                    if let Some(__v) = __f.take_partial(&mut __errors) {
+                        let __check = || -> ::std::result::Result<(), #krate::syn::Error> {
+                            #checks
+                            Ok(())
+                        };
+
+                        match __check() {
+                            Ok(()) => self.#idents = Some(__v),
+                            Err(__err) => __errors.push(__err),
+                        }
+                    }
+                )*
+
+                __errors.extend(__f.unknown_keys());
+                __errors
+            }
+        })
+        .method(quote! {
+            /// Builds the options, failing if a required field isn't set or
+            /// the fields that are set break a relation between keys.
+            pub fn build(self) -> ::std::result::Result<#type_name #ty_generics, #krate::syn::Error> {
+                let __f = #krate::runtime::Present::new(&[
+                    #((#keys, self.#idents.is_some()),)*
+                ]);
+                #relations
+
+                Ok(#type_name {
+                    #(#unwrapper)*
+                })
+            }
+        })
+        .build();
+
+    let default = ImplBuilder::new(syn::parse_quote!(::std::default::Default), &builder_name)
+        .with_generics(generics)
+        .method(quote! {
+            fn default() -> Self {
+                #builder_name {
+                    #(#idents: None,)*
+                }
+            }
+        })
+        .build();
+
+    let constructor = ImplBuilder::inherent(type_name)
+        .with_generics(generics)
+        .method(quote! {
+            /// Creates a builder with no fields set.
+            pub fn builder() -> #builder_name #ty_generics {
+                ::std::default::Default::default()
+            }
+        })
+        .build();
+
+    let partial = ImplBuilder::inherent(type_name)
+        .with_generics(generics)
+        .bound_params(&syn::parse_quote!(#krate::meta::FromMeta))
+        .method(quote! {
+            /// Parses every field that it can, instead of stopping at the
+            /// first error.
+            pub fn from_meta_partial(
+                __m: &#krate::meta::MetaValue,
+            ) -> #krate::meta::Partial<#builder_name #ty_generics> {
+                #config
+                let mut __b = Self::builder();
+                let __errors = __b.merge_partial(__m);
+
+                #krate::meta::Partial {
+                    value: __b,
+                    errors: __errors,
+                }
+            }
+        })
+        .build();
+
+    Ok(quote! {
+        #[doc = #doc]
+        #vis struct #builder_name<#params> #where_clause {
+            #(pub #idents: ::std::option::Option<#tys>,)*
+        }
+
+        #methods
+        #default
+        #constructor
+        #partial
+    })
+}
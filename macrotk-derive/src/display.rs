@@ -0,0 +1,138 @@
+//! Writing derived types back as meta, for `#[macrotk(display)]`.
+
+use syn::spanned::Spanned as _;
+use syn::{Data, DataEnum, Error, Fields, Generics, Ident, LitStr, Path};
+
+use quote::quote;
+
+use proc_macro2::TokenStream;
+
+use macrotk_core::enums::{match_variants, VariantBinding};
+use macrotk_core::impls::ImplBuilder;
+
+use crate::{named_fields, NamedField};
+
+/// Generates `ToMeta` and `Display` impls.
+pub fn display(
+    krate: &Path,
+    type_name: &Ident,
+    generics: &Generics,
+    data: &Data,
+    untagged: bool,
+) -> Result<TokenStream, Error> {
+    let body = match data {
+        Data::Struct(s) => {
+            let entries = named_fields(&s.fields)?
+                .iter()
+                .map(|field| {
+                    let ident = &field.ident;
+                    entry(krate, &field.key(), quote!(&self.#ident))
+                })
+                .collect::<Vec<_>>();
+
+            quote!(Some(#krate::runtime::root([#(#entries),*])))
+        }
+        Data::Enum(e) => enum_body(krate, type_name, e, untagged)?,
+        Data::Union(_) => return Ok(TokenStream::new()),
+    };
+
+    let to_meta: Path = syn::parse_quote!(#krate::meta::ToMeta);
+
+    let to_meta_impl = ImplBuilder::new(to_meta.clone(), type_name)
+        .with_generics(generics)
+        .bound_params(&to_meta)
+        .method(quote! {
+            fn to_meta(&self) -> ::std::option::Option<#krate::meta::MetaValue> {
+                #body
+            }
+        })
+        .build();
+
+    let display_impl = ImplBuilder::new(syn::parse_quote!(::std::fmt::Display), type_name)
+        .with_generics(generics)
+        .bound_params(&to_meta)
+        .method(quote! {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                match #to_meta::to_meta(self) {
+                    Some(meta) => ::std::fmt::Display::fmt(&meta, f),
+                    None => Ok(()),
+                }
+            }
+        })
+        .build();
+
+    Ok(quote!(#to_meta_impl #display_impl))
+}
+
+fn enum_body(
+    krate: &Path,
+    type_name: &Ident,
+    e: &DataEnum,
+    untagged: bool,
+) -> Result<TokenStream, Error> {
+    // check the fields up front, since the closure can't fail
+    for variant in e.variants.iter() {
+        match (untagged, &variant.fields) {
+            (true, Fields::Unnamed(fields)) if fields.unnamed.len() != 1 => {
+                return Err(Error::new(
+                    fields.span(),
+                    "untagged variants can only have one unnamed field",
+                ));
+            }
+            (true, Fields::Unnamed(_)) | (_, Fields::Unit) => (),
+            _ => {
+                named_fields(&variant.fields)?;
+            }
+        }
+    }
+
+    let arms = match_variants(type_name, &e.variants, |binding| {
+        let name = macrotk_core::ident::snake_case(&binding.variant.ident);
+
+        let value = match &binding.variant.fields {
+            Fields::Unit => quote!(#krate::runtime::root([Some(#krate::runtime::path(#name))])),
+            Fields::Unnamed(_) => {
+                let (_, local) = &binding.bindings[0];
+                return quote!(#krate::meta::ToMeta::to_meta(#local));
+            }
+            Fields::Named(_) => {
+                let entries = binding_entries(krate, binding);
+                quote!(#krate::runtime::root([#(#entries),*]))
+            }
+        };
+
+        match (untagged, &binding.variant.fields) {
+            (true, _) | (false, Fields::Unit) => quote!(Some(#value)),
+            (false, _) => quote! {
+                Some(#krate::runtime::root([
+                    Some(#krate::meta::MetaValue::entry(#name, #value)),
+                ]))
+            },
+        }
+    });
+
+    Ok(quote! {
+        match self {
+            #arms
+        }
+    })
+}
+
+fn binding_entries(krate: &Path, binding: &VariantBinding) -> Vec<TokenStream> {
+    binding.bindings.iter()
+        .filter_map(|(field, local)| {
+            // the fields were checked up front
+            let field = NamedField::new(field).ok()?;
+            Some(entry(krate, &field.key(), quote!(#local)))
+        })
+        .collect()
+}
+
+/// The entry of the field read from `key`, given an expression that borrows
+/// it.
+fn entry(krate: &Path, key: &LitStr, value: TokenStream) -> TokenStream {
+    quote! {
+        #krate::meta::ToMeta::to_meta(#value)
+            .map(|__v| #krate::meta::MetaValue::entry(#key, __v))
+    }
+}
@@ -1,126 +1,900 @@
 use syn::spanned::Spanned as _;
-use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr, Ident, Path, Token};
-use syn::punctuated::Punctuated;
+use syn::{Data, DataEnum, DeriveInput, Error, Fields, LitStr, Ident, Path};
 
 use quote::quote;
 use quote::ToTokens as _;
 
+use proc_macro2::TokenStream;
+
+use macrotk_core::impls::ImplBuilder;
+use macrotk_core::meta::{FromMeta as _, MetaList, MetaValue};
+
+mod builder;
+mod display;
+mod merge;
+mod parse_meta;
+mod schema;
+
 struct NamedField {
     use_default: bool,
+    /// A range the value must be in, like `1..=64`, alongside its tokens.
+    range: Option<(String, TokenStream)>,
+    /// A pattern string values must match.
+    matches: Option<String>,
+    /// Bounds on the number of items of a list.
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+    /// Whether the items of a list must be unique.
+    unique: bool,
+    /// Keys that can't be given alongside this one.
+    conflicts_with: Vec<LitStr>,
+    /// Keys that have to be given alongside this one.
+    requires: Vec<LitStr>,
+    /// A foreign attribute `from_attributes` reads this from, like `serde`.
+    foreign: Option<LitStr>,
+    /// Old keys that still parse into this field, with a warning.
+    renamed_from: Vec<LitStr>,
+    /// The key this is read from, if it isn't the name of the field.
+    rename: Option<LitStr>,
     ident: Ident,
+    ty: syn::Type,
 }
 
 impl NamedField {
     pub fn new(f: &syn::Field) -> Result<NamedField, Error> {
-        // figure out if we should use default
         let mut use_default = false;
+        let mut range = None;
+        let mut matches = None;
+        let mut min_items = None;
+        let mut max_items = None;
+        let mut unique = false;
+        let mut conflicts_with = Vec::new();
+        let mut requires = Vec::new();
+        let mut foreign = None;
+        let mut renamed_from = Vec::new();
+        let mut rename = None;
 
-        for attr in f.attrs.iter() {
-            if attr.path
-                .get_ident()
-                .map(|i| i.to_string() == "macrotk")
-                .unwrap_or_default() 
-            {
-                let args: Punctuated<Path, Token![,]> = 
-                    attr.parse_args_with(Punctuated::parse_terminated)?;
-
-                for attr in args.iter() {
-                    if let Some(s) = attr.get_ident()
-                    {
-                        match &s.to_string()[..] {
-                            "default" => use_default = true,
-                            _ => {
-                                return Err(Error::new(
-                                    s.span(),
-                                    format!("unexpected: {}", s),
-                                ))
-                            }
-                        }
-                    } else {
-                        return Err(Error::new(
-                            attr.span(),
-                            format!("unexpected: {}", attr.into_token_stream().to_string()),
-                        ))
-                    }
+        for meta in MetaList::from_attributes(&f.attrs, "macrotk")?.list.iter() {
+            match meta.name().map(ToString::to_string).as_deref() {
+                Some("default") => {
+                    meta.path()?;
+                    use_default = true;
+                }
+                Some("range") => {
+                    let lit = LitStr::from_meta(meta.value())?;
+                    let tokens = syn::parse_str::<TokenStream>(&lit.value())
+                        .map_err(|_| Error::new(lit.span(), "expected a range, like \"1..=64\""))?;
+
+                    range = Some((lit.value(), macrotk_core::tokens::respan(tokens, lit.span())));
+                }
+                Some("matches") => matches = Some(pattern(meta)?),
+                Some("min_items") => min_items = Some(usize::from_meta(meta.value())?),
+                Some("max_items") => max_items = Some(usize::from_meta(meta.value())?),
+                Some("unique") => {
+                    meta.path()?;
+                    unique = true;
                 }
+                Some("conflicts_with") => conflicts_with.push(LitStr::from_meta(meta.value())?),
+                Some("requires") => requires.push(LitStr::from_meta(meta.value())?),
+                Some("foreign") => {
+                    let name = LitStr::from_meta(meta.value())?;
+                    name.parse::<Ident>()?;
+
+                    foreign = Some(name);
+                }
+                Some("renamed_from") => {
+                    let name = LitStr::from_meta(meta.value())?;
+                    name.parse::<Ident>()?;
+
+                    renamed_from.push(name);
+                }
+                Some("rename") => rename = Some(LitStr::from_meta(meta.value())?),
+                _ => return Err(unexpected(meta)),
             }
         }
 
         Ok(NamedField {
             use_default,
+            range,
+            matches,
+            min_items,
+            max_items,
+            unique,
+            conflicts_with,
+            requires,
+            foreign,
+            renamed_from,
+            rename,
             ident: f.ident.clone().unwrap(),
+            ty: f.ty.clone(),
+        })
+    }
+
+    /// The key this is read from.
+    fn key(&self) -> LitStr {
+        match &self.rename {
+            Some(rename) => rename.clone(),
+            None => LitStr::new(&self.ident.to_string(), self.ident.span()),
+        }
+    }
+
+    /// Whether the value has to be checked after parsing.
+    fn checked(&self) -> bool {
+        self.range.is_some()
+            || self.matches.is_some()
+            || self.min_items.is_some()
+            || self.max_items.is_some()
+            || self.unique
+    }
+}
+
+/// Takes the pattern of `matches`, checking that it compiles.
+#[cfg(feature = "regex")]
+fn pattern(meta: &MetaValue) -> Result<String, Error> {
+    let lit = LitStr::from_meta(meta.value())?;
+
+    regex::Regex::new(&lit.value())
+        .map(|_| lit.value())
+        .map_err(|err| Error::new(lit.span(), err))
+}
+
+#[cfg(not(feature = "regex"))]
+fn pattern(meta: &MetaValue) -> Result<String, Error> {
+    Err(Error::new_spanned(meta, "`matches` requires the `regex` feature"))
+}
+
+pub(crate) struct Container {
+    untagged: bool,
+    schema: bool,
+    /// Whether to generate a builder and `from_meta_partial`.
+    builder: bool,
+    /// Whether unknown keys and values that don't parse are tolerated.
+    lenient: bool,
+    impl_default: bool,
+    display: bool,
+    /// The names of the attributes `from_attributes` reads.
+    attributes: Vec<LitStr>,
+    /// The file in the manifest dir with baseline options.
+    config: Option<LitStr>,
+    /// The field a lone value is read into, as in `#[table("users")]`.
+    word: Option<Ident>,
+    /// Keys of which exactly one must be present.
+    exactly_one_of: Vec<Vec<Ident>>,
+    /// Keys of which at least one must be present.
+    at_least_one_of: Vec<Vec<Ident>>,
+}
+
+impl Container {
+    pub fn new(attrs: &[syn::Attribute]) -> Result<Container, Error> {
+        let mut untagged = false;
+        let mut schema = false;
+        let mut builder = false;
+        let mut lenient = false;
+        let mut impl_default = false;
+        let mut display = false;
+        let mut attributes = Vec::new();
+        let mut config = None;
+        let mut word = None;
+        let mut exactly_one_of = Vec::new();
+        let mut at_least_one_of = Vec::new();
+
+        for meta in MetaList::from_attributes(attrs, "macrotk")?.list.iter() {
+            let flag = match meta.name().map(ToString::to_string).as_deref() {
+                Some("untagged") => &mut untagged,
+                Some("schema") => &mut schema,
+                Some("builder") => &mut builder,
+                Some("lenient") => &mut lenient,
+                Some("impl_default") => &mut impl_default,
+                Some("display") => &mut display,
+                Some("attribute") => {
+                    let name = LitStr::from_meta(meta.value())?;
+                    name.parse::<Ident>()?;
+
+                    attributes.push(name);
+                    continue;
+                }
+                Some("config") => {
+                    config = Some(config_file(meta)?);
+                    continue;
+                }
+                Some("word") => {
+                    word = Some(LitStr::from_meta(meta.value())?.parse::<Ident>()?);
+                    continue;
+                }
+                Some("exactly_one_of") => {
+                    exactly_one_of.push(key_group(meta)?);
+                    continue;
+                }
+                Some("at_least_one_of") => {
+                    at_least_one_of.push(key_group(meta)?);
+                    continue;
+                }
+                _ => return Err(unexpected(meta)),
+            };
+
+            meta.path()?;
+            *flag = true;
+        }
+
+        Ok(Container {
+            untagged,
+            schema,
+            builder,
+            lenient,
+            impl_default,
+            display,
+            attributes,
+            config,
+            word,
+            exactly_one_of,
+            at_least_one_of,
         })
     }
+
+    fn has_key_groups(&self) -> bool {
+        !self.exactly_one_of.is_empty() || !self.at_least_one_of.is_empty()
+    }
 }
 
+/// Takes the keys of a list like `exactly_one_of(path, url)`.
+fn key_group(meta: &MetaValue) -> Result<Vec<Ident>, Error> {
+    let list = meta.list()?;
+
+    if list.list.len() < 2 {
+        return Err(Error::new_spanned(meta, "expected at least two keys"));
+    }
+
+    list.list.iter()
+        .map(|key| {
+            key.path()?
+                .get_ident()
+                .cloned()
+                .ok_or_else(|| Error::new_spanned(key, "expected a key"))
+        })
+        .collect()
+}
+
+/// Takes the file name of `config`.
+#[cfg(feature = "config")]
+fn config_file(meta: &MetaValue) -> Result<LitStr, Error> {
+    LitStr::from_meta(meta.value())
+}
+
+#[cfg(not(feature = "config"))]
+fn config_file(meta: &MetaValue) -> Result<LitStr, Error> {
+    Err(Error::new_spanned(meta, "`config` requires the `config` feature"))
+}
+
+fn unexpected(meta: &MetaValue) -> Error {
+    Error::new_spanned(meta, format!("unexpected: {}", meta.to_token_stream()))
+}
+
+/// Derives `FromMeta`, configured with `#[macrotk(...)]` attributes.
+///
+/// `#[macrotk(builder)]` on a struct also generates `{Type}Builder` and
+/// `from_meta_partial`, which returns every field that parsed on a builder.
+/// `from_meta_partial` needs the builder to hold the fields, so it is only
+/// generated with `builder`.
+///
+/// `#[macrotk(renamed_from = "old")]` on a field keeps reading the old key,
+/// with a deprecation warning. Warnings are only emitted by macros whose
+/// entry point goes through `macrotk::entry::expand` or `expand_attribute`.
+/// Other macros have to append `macrotk::warnings::take()` to their output.
 #[proc_macro_derive(FromMeta, attributes(macrotk))]
 pub fn derive_from_meta(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let item = parse_macro_input!(item as DeriveInput);
+    macrotk_core::entry::expand("FromMeta", item.into(), |item| {
+        from_meta_impl(syn::parse2(item)?)
+    })
+    .into()
+}
+
+/// Parses the arguments of an attribute in a hand-written macro.
+///
+/// ```ignore
+/// let args = parse_meta!(attr => { name: LitStr, retries: i64 = 3, verbose?: bool })?;
+/// ```
+///
+/// This evaluates to a `Result` of a struct with a field for each key. Keys
+/// with a default fall back to it when they are missing, and keys marked `?`
+/// are wrapped in an [`Option`]. Any other key is an error.
+#[proc_macro]
+pub fn parse_meta(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    parse_meta::parse_meta(input.into())
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(MergeMeta)]
+pub fn derive_merge_meta(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    macrotk_core::entry::expand("MergeMeta", item.into(), |item| {
+        merge::merge_meta_impl(syn::parse2(item)?)
+    })
+    .into()
+}
 
+fn from_meta_impl(item: DeriveInput) -> Result<TokenStream, Error> {
     // get name
     let type_name = item.ident;
+    let generics = item.generics;
+    let krate = macrotk_core::crate_path("macrotk");
+    let container = Container::new(&item.attrs)?;
 
-    let item = match item.data {
-        Data::Struct(s) => s,
-        Data::Enum(e) => {
-            return Error::new(e.enum_token.span(), "only structs are supported")
-                .into_compile_error()
-                .into()
+    if container.untagged && !matches!(item.data, Data::Enum(_)) {
+        return Err(Error::new(type_name.span(), "only enums can be untagged"));
+    }
+
+    if container.lenient && !matches!(item.data, Data::Struct(_)) {
+        return Err(Error::new(type_name.span(), "only structs can be lenient"));
+    }
+
+    if container.word.is_some() && !matches!(item.data, Data::Struct(_)) {
+        return Err(Error::new(type_name.span(), "only structs can have a word form"));
+    }
+
+    if container.config.is_some() && !matches!(item.data, Data::Struct(_)) {
+        return Err(Error::new(type_name.span(), "only structs can read config files"));
+    }
+
+    if container.has_key_groups() && !matches!(item.data, Data::Struct(_)) {
+        return Err(Error::new(type_name.span(), "only structs can have key groups"));
+    }
+
+    let schema = match container.schema {
+        true => Some(schema::describe(&item.data, container.untagged)?),
+        false => None,
+    };
+    #[cfg(feature = "json-schema")]
+    let json_schema = match container.schema {
+        true => Some(schema::describe_json(&item.data, container.untagged)?),
+        false => None,
+    };
+    #[cfg(not(feature = "json-schema"))]
+    let json_schema: Option<String> = None;
+
+    let field_specs = schema::field_specs(&krate, &item.data, container.untagged)?;
+
+    let builder = match (container.builder, &item.data) {
+        (true, Data::Struct(s)) => {
+            Some(builder::builder(&krate, &container, &item.vis, &type_name, &generics, &s.fields)?)
         }
-        Data::Union(e) => {
-            return Error::new(e.union_token.span(), "only structs are supported")
-                .into_compile_error()
-                .into()
+        (true, _) => return Err(Error::new(type_name.span(), "only structs can have builders")),
+        (false, _) => None,
+    };
+
+    let display = match container.display {
+        true => Some(display::display(&krate, &type_name, &generics, &item.data, container.untagged)?),
+        false => None,
+    };
+
+    let foreign = match &item.data {
+        Data::Struct(s) => named_fields(&s.fields)?
+            .into_iter()
+            .filter_map(|field| Some((field.key(), field.foreign?)))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    if container.attributes.is_empty() && !foreign.is_empty() {
+        return Err(Error::new(
+            foreign[0].1.span(),
+            "foreign attributes are only read with #[macrotk(attribute = \"...\")]",
+        ));
+    }
+
+    let from_attributes = match container.attributes.is_empty() {
+        false => Some(from_attributes(&krate, &type_name, &generics, &container.attributes, &foreign)),
+        true => None,
+    };
+
+    let default = match (container.impl_default, &item.data) {
+        (true, Data::Struct(s)) => Some(default_impl(&type_name, &generics, &s.fields)?),
+        (true, _) => {
+            return Err(Error::new(type_name.span(), "only structs can implement Default"))
         }
+        (false, _) => None,
     };
 
-    // parse all of these fields
-    let fields = match item.fields {
-        Fields::Named(fields) => {
-            match fields.named.iter()
-                .map(NamedField::new)
-                .collect::<Result<Vec<_>, Error>>()
-            {
-                Ok(fields) => fields,
-                Err(err) => return err.into_compile_error().into(),
+    let mut lenient = None;
+    let body = match item.data {
+        Data::Struct(s) => {
+            let fields = named_fields(&s.fields)?;
+            let construct = construct(&quote!(#type_name), &fields, container.lenient);
+            let table = field_table(&krate, &fields);
+            let relations = relations(&container, &fields)?;
+            let word = word_expr(&krate, &container, &fields)?.map(|word| quote! {
+                let __w = #word?;
+                let __m: &#krate::meta::MetaValue = &__w;
+            });
+            let config = config_expr(&krate, &container).map(|config| quote! {
+                let __c = #config?;
+                let __m: &#krate::meta::MetaValue = &__c;
+            });
+
+            let body = quote! {
+                #word
+                #config
+                let mut __f = #table?;
+                #relations
+            };
+
+            match container.lenient {
+                true => {
+                    lenient = Some(lenient_impl(&krate, &type_name, &generics, quote! {
+                        let mut __errors = ::std::vec::Vec::new();
+                        #body
+
+                        let __v = #construct;
+                        __errors.extend(__f.unknown_keys());
+
+                        Ok(#krate::meta::Partial {
+                            value: __v,
+                            errors: __errors,
+                        })
+                    }));
+
+                    // what was tolerated, like keys for other tools, is
+                    // only reported through `from_meta_lenient`
+                    quote!(Self::from_meta_lenient(__m).map(|__p| __p.value))
+                }
+                false => quote! {
+                    #body
+                    __f.deny_unknown()?;
+
+                    Ok(#construct)
+                },
             }
         }
-        e => {
-            return Error::new(e.span(), "struct can only have named fields")
-                .into_compile_error()
-                .into()
+        Data::Enum(e) if container.untagged => untagged_body(&krate, &type_name, &e)?,
+        Data::Enum(e) => enum_body(&krate, &type_name, &e)?,
+        Data::Union(e) => {
+            return Err(Error::new(e.union_token.span(), "unions are not supported"))
         }
     };
 
-    let unwrapper = fields.iter()
-        .map(|field| {
-            let name = &field.ident;
-            let name_str = LitStr::new(&name.to_string(), name.span());
+    let from_meta: Path = syn::parse_quote!(#krate::meta::FromMeta);
+
+    let expanded = ImplBuilder::new(from_meta.clone(), &type_name)
+        .with_generics(&generics)
+        .bound_params(&from_meta)
+        .method(quote! {
+            fn from_meta(
+                __m: &#krate::meta::MetaValue,
+            ) -> ::std::result::Result<Self, #krate::syn::Error> {
+                #body
+            }
+        })
+        .build();
+
+    let meta_schema = ImplBuilder::new(syn::parse_quote!(#krate::schema::MetaSchema), &type_name)
+        .with_generics(&generics)
+        .method(quote! {
+            fn fields() -> &'static [#krate::schema::FieldSpec] {
+                #field_specs
+            }
+        })
+        .build();
+
+    let schema = schema.map(|schema| {
+        let json_schema = json_schema.map(|json_schema| quote! {
+            /// The options this accepts as JSON, for tooling.
+            pub const JSON_SCHEMA: &'static str = #json_schema;
+        });
+
+        ImplBuilder::inherent(&type_name)
+            .with_generics(&generics)
+            .item(quote! {
+                /// The options this accepts, for use in error messages.
+                pub const SCHEMA: &'static str = #schema;
+            })
+            .item(json_schema)
+            .build()
+    });
+
+    Ok(quote!(#expanded #meta_schema #schema #builder #default #display #from_attributes #lenient))
+}
+
+/// Generates `ATTRIBUTES` and `from_attributes`, which reads the attributes
+/// named by `#[macrotk(attribute = "...")]`.
+///
+/// `foreign` pairs fields with the foreign attribute they're read from.
+fn from_attributes(
+    krate: &Path,
+    type_name: &Ident,
+    generics: &syn::Generics,
+    attributes: &[LitStr],
+    foreign: &[(LitStr, LitStr)],
+) -> TokenStream {
+    let keys = foreign.iter().map(|(key, _)| key);
+    let names = foreign.iter().map(|(_, name)| name);
+
+    ImplBuilder::inherent(type_name)
+        .with_generics(generics)
+        .bound_params(&syn::parse_quote!(#krate::meta::FromMeta))
+        .item(quote! {
+            /// The names of the attributes this is read from.
+            pub const ATTRIBUTES: &'static [&'static str] = &[#(#attributes),*];
+        })
+        .method(quote! {
+            /// Parses the arguments of every attribute named in
+            /// [`ATTRIBUTES`](Self::ATTRIBUTES).
+            pub fn from_attributes(
+                attrs: &[#krate::syn::Attribute],
+            ) -> ::std::result::Result<Self, #krate::syn::Error> {
+                let mut __m = #krate::meta::MetaList::from_attributes_any(attrs, Self::ATTRIBUTES)?;
+                #(#krate::runtime::push_foreign(&mut __m, attrs, #keys, #names)?;)*
+
+                #krate::meta::FromMeta::from_meta(&__m.into())
+            }
+        })
+        .build()
+}
+
+/// Generates `from_meta_lenient` from its `body`.
+fn lenient_impl(
+    krate: &Path,
+    type_name: &Ident,
+    generics: &syn::Generics,
+    body: TokenStream,
+) -> TokenStream {
+    ImplBuilder::inherent(type_name)
+        .with_generics(generics)
+        .bound_params(&syn::parse_quote!(#krate::meta::FromMeta))
+        .method(quote! {
+            /// Parses `meta`, tolerating unknown keys and values that don't
+            /// parse for fields with defaults, which are returned as errors
+            /// alongside the value.
+            pub fn from_meta_lenient(
+                __m: &#krate::meta::MetaValue,
+            ) -> ::std::result::Result<#krate::meta::Partial<Self>, #krate::syn::Error> {
+                #body
+            }
+        })
+        .build()
+}
+
+/// Generates a `Default` impl that gives every field the default it gets
+/// when it is missing, so it agrees with parsing an empty attribute.
+fn default_impl(type_name: &Ident, generics: &syn::Generics, fields: &Fields) -> Result<TokenStream, Error> {
+    let fields = named_fields(fields)?;
+
+    if let Some(field) = fields.iter().find(|field| !field.use_default) {
+        return Err(Error::new(
+            field.ident.span(),
+            format!("`{}` needs #[macrotk(default)] for #[macrotk(impl_default)]", field.ident),
+        ));
+    }
+
+    let idents = fields.iter().map(|field| &field.ident);
+    let default: Path = syn::parse_quote!(::std::default::Default);
+
+    Ok(ImplBuilder::new(default.clone(), type_name)
+        .with_generics(generics)
+        .bound_params(&default)
+        .method(quote! {
+            fn default() -> Self {
+                #type_name {
+                    #(#idents: ::std::default::Default::default(),)*
+                }
+            }
+        })
+        .build())
+}
+
+/// Generates a match over the variants of an enum, each selected by its
+/// snake_case name.
+fn enum_body(krate: &Path, type_name: &Ident, e: &DataEnum) -> Result<TokenStream, Error> {
+    let mut names = Vec::new();
+    let mut arms = Vec::new();
+
+    for variant in e.variants.iter() {
+        let ident = &variant.ident;
+        let name = LitStr::new(&macrotk_core::ident::snake_case(ident), ident.span());
+
+        let arm = match &variant.fields {
+            Fields::Unit => quote! {
+                #name => {
+                    #krate::runtime::unit(__e)?;
+
+                    Ok(#type_name::#ident)
+                }
+            },
+            fields => {
+                let fields = named_fields(fields)?;
+                let construct = construct(&quote!(#type_name::#ident), &fields, false);
+                let table = field_table(krate, &fields);
+                let relations = field_relations(&fields)?;
+
+                quote! {
+                    #name => {
+                        let __m = __e;
+                        let mut __f = #table?;
+                        #relations
+                        __f.deny_unknown()?;
+
+                        Ok(#construct)
+                    }
+                }
+            }
+        };
+
+        names.push(name);
+        arms.push(arm);
+    }
+
+    // fieldless enums with explicit discriminants can also be written as
+    // their discriminant
+    let discriminants = e.variants.iter().all(|v| matches!(v.fields, Fields::Unit))
+        && e.variants.iter().any(|v| v.discriminant.is_some());
+
+    let by_discriminant = discriminants.then(|| {
+        let idents = e.variants.iter().map(|v| &v.ident);
+
+        quote! {
+            if let Some((__d, __lit)) = #krate::runtime::discriminant(__m)? {
+                #(
+                    if __d == #type_name::#idents as i128 {
+                        return Ok(#type_name::#idents);
+                    }
+                )*
+
+                return Err(#krate::runtime::unknown_discriminant(__lit));
+            }
+        }
+    });
+
+    Ok(quote! {
+        #by_discriminant
+
+        let (__name, __e) = #krate::runtime::variant(__m)?;
+
+        match &__name[..] {
+            #(#arms)*
+            __name => Err(#krate::runtime::unknown_variant(__e, __name, &[#(#names),*])),
+        }
+    })
+}
+
+/// Generates code that tries each variant of an enum in order, taking the
+/// first that parses.
+fn untagged_body(krate: &Path, type_name: &Ident, e: &DataEnum) -> Result<TokenStream, Error> {
+    let mut attempts = Vec::new();
+
+    for variant in e.variants.iter() {
+        let ident = &variant.ident;
+
+        let attempt = match &variant.fields {
+            Fields::Unit => {
+                let name = LitStr::new(&macrotk_core::ident::snake_case(ident), ident.span());
 
-            if field.use_default {
                 quote! {
-                    #name: __m.get(#name_str).unwrap_or(Ok(Default::default()))?,
+                    #krate::runtime::unit_variant(__m, #name).map(|_| #type_name::#ident)
                 }
-            } else {
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => quote! {
+                #krate::meta::FromMeta::from_meta(__m).map(#type_name::#ident)
+            },
+            Fields::Unnamed(fields) => {
+                return Err(Error::new(
+                    fields.span(),
+                    "untagged variants can only have one unnamed field",
+                ))
+            }
+            fields => {
+                let fields = named_fields(fields)?;
+                let construct = construct(&quote!(#type_name::#ident), &fields, false);
+                let table = field_table(krate, &fields);
+                let relations = field_relations(&fields)?;
+
                 quote! {
-                    #name: __m.get(#name_str).ok_or(::macrotk::syn::Error::new(::macrotk::Span::call_site(), ::std::concat!("missing value for ", #name_str)))??,
+                    let mut __f = #table?;
+                    #relations
+                    __f.deny_unknown()?;
+
+                    Ok(#construct)
                 }
             }
+        };
+
+        attempts.push(quote! {
+            let __attempt = || -> ::std::result::Result<Self, #krate::syn::Error> {
+                #attempt
+            };
+
+            match __attempt() {
+                Ok(__v) => return Ok(__v),
+                Err(__err) => __errors.push(__err),
+            }
         });
+    }
 
-    let expanded = quote! {
-        impl ::macrotk::meta::FromMeta for #type_name {
-            fn from_meta(
-                __m: &::macrotk::meta::MetaValue,
-            ) -> ::std::result::Result<Self, ::macrotk::syn::Error> {
-                let __m = __m.list()?;
+    Ok(quote! {
+        let mut __errors = ::std::vec::Vec::new();
+
+        #(#attempts)*
+
+        Err(#krate::runtime::no_variant_matched(__m, __errors))
+    })
+}
+
+pub(crate) fn named_fields(fields: &Fields) -> Result<Vec<NamedField>, Error> {
+    let fields = match fields {
+        Fields::Named(fields) => {
+            fields.named.iter()
+                .map(NamedField::new)
+                .collect::<Result<Vec<_>, Error>>()?
+        }
+        e => return Err(Error::new(e.span(), "only named fields are supported")),
+    };
+
+    check_keys(&fields)?;
 
-                Ok(#type_name {
-                    #(#unwrapper)*
-                })
+    Ok(fields)
+}
+
+/// Checks that no two fields read the same key, which would make it
+/// ambiguous which one a key is for.
+fn check_keys(fields: &[NamedField]) -> Result<(), Error> {
+    let mut keys = std::collections::HashMap::new();
+
+    for field in fields {
+        if let Some(other) = keys.insert(field.key().value(), &field.ident) {
+            return Err(Error::new(
+                field.key().span(),
+                format!("the key `{}` is already read by `{}`", field.key().value(), other),
+            ));
+        }
+    }
+
+    for field in fields {
+        for old in field.renamed_from.iter() {
+            if let Some(other) = keys.insert(old.value(), &field.ident) {
+                return Err(Error::new(
+                    old.span(),
+                    format!("the key `{}` is already read by `{}`", old.value(), other),
+                ));
             }
         }
+    }
+
+    Ok(())
+}
+
+/// Creates a field table over `__m`, as a `Result`.
+pub(crate) fn field_table(krate: &Path, fields: &[NamedField]) -> TokenStream {
+    let names = fields.iter().map(NamedField::key);
+
+    let renames = fields.iter()
+        .flat_map(|field| {
+            let name = field.key();
+
+            field.renamed_from.iter().map(move |old| quote!((#old, #name)))
+        });
+
+    quote!(#krate::runtime::FieldTable::new(__m, &[#(#names),*], &[#(#renames),*]))
+}
+
+/// Generates the checks between the keys in the field table `__f`, before
+/// any of them are parsed.
+pub(crate) fn relations(container: &Container, fields: &[NamedField]) -> Result<TokenStream, Error> {
+    let mut checks = TokenStream::new();
+
+    let groups = container.exactly_one_of.iter()
+        .map(|group| (group, quote!(check_exactly_one_of)))
+        .chain(container.at_least_one_of.iter().map(|group| (group, quote!(check_at_least_one_of))));
+
+    for (group, check) in groups {
+        let keys = group.iter()
+            .map(|key| field_key(fields, key))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        checks.extend(quote!(__f.#check(&[#(#keys),*])?;));
+    }
+
+    checks.extend(field_relations(fields)?);
+
+    Ok(checks)
+}
+
+/// Generates an expression that expands the word form of `__m`, if the
+/// container has one.
+pub(crate) fn word_expr(krate: &Path, container: &Container, fields: &[NamedField]) -> Result<Option<TokenStream>, Error> {
+    let word = match &container.word {
+        Some(word) => word,
+        None => return Ok(None),
     };
 
-    proc_macro::TokenStream::from(expanded)
+    let key = field_key(fields, word)?;
+    let keys = fields.iter().map(NamedField::key);
+
+    Ok(Some(quote!(#krate::runtime::word(__m, #key, &[#(#keys),*]))))
+}
+
+/// Generates an expression that adds the container's config file to `__m`,
+/// if it has one.
+pub(crate) fn config_expr(krate: &Path, container: &Container) -> Option<TokenStream> {
+    container.config.as_ref().map(|file| quote!(#krate::runtime::with_config(__m, #file)))
+}
+
+/// Generates the checks that fields declare between themselves and other
+/// keys in the field table `__f`.
+fn field_relations(fields: &[NamedField]) -> Result<TokenStream, Error> {
+    let mut checks = TokenStream::new();
+
+    for field in fields {
+        let key = field.key();
+
+        for other in field.conflicts_with.iter() {
+            let other = field_key(fields, &other.parse()?)?;
+
+            checks.extend(quote!(__f.check_conflict(#key, #other)?;));
+        }
+
+        for other in field.requires.iter() {
+            let other = field_key(fields, &other.parse()?)?;
+
+            checks.extend(quote!(__f.check_requires(#key, #other)?;));
+        }
+    }
+
+    Ok(checks)
+}
+
+/// The key of the field named `ident`, checking that there is one.
+fn field_key(fields: &[NamedField], ident: &Ident) -> Result<LitStr, Error> {
+    match fields.iter().find(|field| field.ident == *ident) {
+        Some(field) => Ok(LitStr::new(&field.key().value(), ident.span())),
+        None => Err(Error::new(ident.span(), format!("no field named `{}`", ident))),
+    }
+}
+
+/// Constructs `path` from the field table `__f`.
+///
+/// If `lenient`, fields with defaults that don't parse fall back to them,
+/// with their errors put in `__errors`.
+fn construct(path: &TokenStream, fields: &[NamedField], lenient: bool) -> TokenStream {
+    let unwrapper = fields.iter()
+        .map(|field| {
+            let name = &field.ident;
+
+            let take = match (field.use_default, lenient) {
+                (true, true) => {
+                    quote!(__f.take_lenient(&mut __errors, ::std::default::Default::default))
+                }
+                (true, false) => quote!(__f.take_or_else(::std::default::Default::default)?),
+                (false, _) => quote!(__f.take()?),
+            };
+
+            match checks(field) {
+                Some(checks) => quote! {
+                    #name: {
+                        let __v = #take;
+                        #checks
+                        __v
+                    },
+                },
+                None => quote!(#name: #take,),
+            }
+        });
+
+    quote!(#path { #(#unwrapper)* })
+}
+
+/// Generates the checks of the field that was just taken from `__f`, as
+/// `__v`.
+///
+/// Each check returns early with `?`.
+pub(crate) fn checks(field: &NamedField) -> Option<TokenStream> {
+    let range = field.range.as_ref().map(|(text, bounds)| {
+        quote!(__f.check_range(&__v, #bounds, #text)?;)
+    });
+
+    let matches = field.matches.as_ref().map(|pattern| {
+        quote!(__f.check_matches(#pattern)?;)
+    });
+
+    let min_items = field.min_items.map(|min| quote!(__f.check_min_items(#min)?;));
+    let max_items = field.max_items.map(|max| quote!(__f.check_max_items(#max)?;));
+    let unique = field.unique.then(|| quote!(__f.check_unique()?;));
+
+    field.checked().then(|| quote!(#range #matches #min_items #max_items #unique))
 }
@@ -0,0 +1,54 @@
+//! `#[derive(MergeMeta)]`.
+
+use syn::spanned::Spanned as _;
+use syn::{Data, DeriveInput, Error, Fields, Index, Path};
+
+use quote::quote;
+
+use proc_macro2::TokenStream;
+
+use macrotk_core::impls::ImplBuilder;
+
+pub fn merge_meta_impl(item: DeriveInput) -> Result<TokenStream, Error> {
+    let type_name = &item.ident;
+    let krate = macrotk_core::crate_path("macrotk");
+    let merge_meta: Path = syn::parse_quote!(#krate::merge::MergeMeta);
+
+    let body = match &item.data {
+        Data::Struct(s) => {
+            let merged = s.fields.iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    let member = match &field.ident {
+                        Some(ident) => quote!(#ident),
+                        None => {
+                            let index = Index::from(i);
+                            quote!(#index)
+                        }
+                    };
+
+                    quote!(#member: #merge_meta::merge(self.#member, other.#member),)
+                });
+
+            match s.fields {
+                Fields::Unit => quote!(other),
+                _ => quote!(#type_name { #(#merged)* }),
+            }
+        }
+        // there is no telling how to layer different variants
+        Data::Enum(_) => quote!(other),
+        Data::Union(e) => {
+            return Err(Error::new(e.union_token.span(), "unions are not supported"))
+        }
+    };
+
+    Ok(ImplBuilder::new(merge_meta.clone(), type_name)
+        .with_generics(&item.generics)
+        .bound_params(&merge_meta)
+        .method(quote! {
+            fn merge(self, other: Self) -> Self {
+                #body
+            }
+        })
+        .build())
+}
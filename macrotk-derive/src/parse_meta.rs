@@ -0,0 +1,148 @@
+//! `parse_meta!`.
+
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{braced, Error, Ident, LitStr, Token, Type};
+
+use quote::quote;
+
+use proc_macro2::{TokenStream, TokenTree};
+
+/// The input of `parse_meta!`, like `tokens => { name: LitStr }`.
+struct Input {
+    tokens: TokenStream,
+    fields: Punctuated<Field, Token![,]>,
+}
+
+/// A field, like `retries: i64 = 3` or `verbose?: bool`.
+struct Field {
+    ident: Ident,
+    optional: bool,
+    ty: Type,
+    default: Option<TokenStream>,
+}
+
+impl Parse for Input {
+    fn parse(p: ParseStream) -> Result<Input, Error> {
+        let tokens = tokens_until(p, |p| p.peek(Token![=>]))?;
+        if tokens.is_empty() {
+            return Err(p.error("expected the tokens to parse"));
+        }
+
+        p.parse::<Token![=>]>()?;
+
+        let content;
+        braced!(content in p);
+
+        Ok(Input {
+            tokens,
+            fields: content.parse_terminated(Field::parse)?,
+        })
+    }
+}
+
+impl Parse for Field {
+    fn parse(p: ParseStream) -> Result<Field, Error> {
+        let ident = p.parse()?;
+        let optional = p.parse::<Option<Token![?]>>()?.is_some();
+        p.parse::<Token![:]>()?;
+        let ty = p.parse()?;
+
+        let default = match p.parse::<Option<Token![=]>>()? {
+            Some(eq) if optional => {
+                return Err(Error::new(eq.span, "an optional field can't have a default"));
+            }
+            Some(_) => {
+                let default = tokens_until(p, |p| p.peek(Token![,]))?;
+                if default.is_empty() {
+                    return Err(p.error("expected a default"));
+                }
+
+                Some(default)
+            }
+            None => None,
+        };
+
+        Ok(Field { ident, optional, ty, default })
+    }
+}
+
+impl Field {
+    /// The key this is read from.
+    fn key(&self) -> LitStr {
+        LitStr::new(&self.ident.to_string(), self.ident.span())
+    }
+}
+
+/// Takes token trees until `end` matches or the input runs out.
+///
+/// Groups are single token trees, so only top-level tokens can end it.
+fn tokens_until<F>(p: ParseStream, end: F) -> Result<TokenStream, Error>
+where F:
+    Fn(ParseStream) -> bool,
+{
+    let mut tokens = TokenStream::new();
+
+    while !p.is_empty() && !end(p) {
+        tokens.extend(Some(p.parse::<TokenTree>()?));
+    }
+
+    Ok(tokens)
+}
+
+pub fn parse_meta(input: TokenStream) -> Result<TokenStream, Error> {
+    let input = syn::parse2::<Input>(input)?;
+    let krate = macrotk_core::crate_path("macrotk");
+
+    let fields = input.fields.iter().collect::<Vec<_>>();
+    let keys = fields.iter().map(|field| field.key()).collect::<Vec<_>>();
+
+    let mut seen = std::collections::HashMap::new();
+    for (field, key) in fields.iter().zip(&keys) {
+        if let Some(other) = seen.insert(key.value(), &field.ident) {
+            return Err(Error::new(
+                key.span(),
+                format!("the key `{}` is already read by `{}`", key.value(), other),
+            ));
+        }
+    }
+
+    let decls = fields.iter()
+        .map(|field| {
+            let Field { ident, ty, .. } = field;
+
+            match field.optional {
+                true => quote!(#ident: ::std::option::Option<#ty>,),
+                false => quote!(#ident: #ty,),
+            }
+        });
+
+    let takes = fields.iter()
+        .map(|field| {
+            let ident = &field.ident;
+
+            let take = match (&field.default, field.optional) {
+                (_, true) => quote!(__f.take_opt()?),
+                (Some(default), false) => quote!(__f.take_or_else(|| #default)?),
+                (None, false) => quote!(__f.take()?),
+            };
+
+            quote!(#ident: #take,)
+        });
+
+    let tokens = &input.tokens;
+
+    Ok(quote! {
+        {
+            struct ParsedMeta {
+                #(#decls)*
+            }
+
+            #krate::runtime::parse_meta(
+                ::std::convert::Into::into(#tokens),
+                &[#(#keys),*],
+                |__f| ::std::result::Result::Ok(ParsedMeta { #(#takes)* }),
+            )
+        }
+    })
+}
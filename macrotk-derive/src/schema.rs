@@ -0,0 +1,227 @@
+//! Human-readable descriptions of the options a derived type accepts.
+
+use std::fmt::Write as _;
+
+use syn::{Data, Error, Fields, Path, Type};
+
+use quote::quote;
+use quote::ToTokens as _;
+
+use proc_macro2::TokenStream;
+
+use crate::{named_fields, NamedField};
+
+/// Generates the `FieldSpec`s of a derived type.
+///
+/// Untagged enums have no keys of their own, so they have no specs.
+pub fn field_specs(krate: &Path, data: &Data, untagged: bool) -> Result<TokenStream, Error> {
+    let specs = match data {
+        Data::Struct(s) => named_fields(&s.fields)?
+            .iter()
+            .map(|field| {
+                let name = field.key();
+                let ty = type_name(&field.ty);
+                let required = !field.use_default;
+
+                quote! {
+                    #krate::schema::FieldSpec {
+                        name: #name,
+                        required: #required,
+                        kind: #krate::schema::FieldKind::Field(#ty),
+                    }
+                }
+            })
+            .collect(),
+        Data::Enum(e) if !untagged => e.variants.iter()
+            .map(|variant| {
+                let name = macrotk_core::ident::snake_case(&variant.ident);
+
+                quote! {
+                    #krate::schema::FieldSpec {
+                        name: #name,
+                        required: false,
+                        kind: #krate::schema::FieldKind::Variant,
+                    }
+                }
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    Ok(quote!(&[#(#specs),*]))
+}
+
+/// Describes the options accepted by a derived type, one per line.
+pub fn describe(data: &Data, untagged: bool) -> Result<String, Error> {
+    let mut out = String::new();
+
+    match data {
+        Data::Struct(s) => describe_fields(&mut out, &named_fields(&s.fields)?, ""),
+        Data::Enum(e) => {
+            out.push_str(if untagged { "any of:\n" } else { "one of:\n" });
+
+            for variant in e.variants.iter() {
+                let name = macrotk_core::ident::snake_case(&variant.ident);
+
+                match &variant.fields {
+                    Fields::Unit => match &variant.discriminant {
+                        Some((_, d)) => {
+                            let _ = writeln!(out, "  {} (or {})", name, d.to_token_stream());
+                        }
+                        None => {
+                            let _ = writeln!(out, "  {}", name);
+                        }
+                    },
+                    Fields::Unnamed(fields) => {
+                        for field in fields.unnamed.iter() {
+                            let _ = writeln!(out, "  {}", type_name(&field.ty));
+                        }
+                    }
+                    fields if untagged => describe_fields(&mut out, &named_fields(fields)?, "  "),
+                    fields => {
+                        let _ = writeln!(out, "  {}(...)", name);
+                        describe_fields(&mut out, &named_fields(fields)?, "    ");
+                    }
+                }
+            }
+        }
+        Data::Union(_) => (),
+    }
+
+    // the trailing newline would look odd in an error message
+    out.truncate(out.trim_end().len());
+
+    Ok(out)
+}
+
+/// Describes the options accepted by a derived type as JSON, for tooling.
+#[cfg(feature = "json-schema")]
+pub fn describe_json(data: &Data, untagged: bool) -> Result<String, Error> {
+    let json = match data {
+        Data::Struct(s) => format!(
+            r#"{{"kind":"struct","fields":{}}}"#,
+            fields_json(&named_fields(&s.fields)?),
+        ),
+        Data::Enum(e) => {
+            let variants = e.variants.iter()
+                .map(|variant| {
+                    let name = json_string(&macrotk_core::ident::snake_case(&variant.ident));
+
+                    Ok(match &variant.fields {
+                        Fields::Unit => {
+                            let discriminant = variant.discriminant.as_ref()
+                                .map(|(_, d)| json_string(&d.to_token_stream().to_string()))
+                                .unwrap_or_else(|| String::from("null"));
+
+                            format!(
+                                r#"{{"name":{},"kind":"unit","discriminant":{}}}"#,
+                                name, discriminant,
+                            )
+                        }
+                        Fields::Unnamed(fields) => {
+                            let types = fields.unnamed.iter()
+                                .map(|field| json_string(&type_name(&field.ty)))
+                                .collect::<Vec<_>>();
+
+                            format!(
+                                r#"{{"name":{},"kind":"newtype","type":{}}}"#,
+                                name, types.join(","),
+                            )
+                        }
+                        fields => format!(
+                            r#"{{"name":{},"kind":"struct","fields":{}}}"#,
+                            name, fields_json(&named_fields(fields)?),
+                        ),
+                    })
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            format!(
+                r#"{{"kind":"enum","untagged":{},"variants":[{}]}}"#,
+                untagged, variants.join(","),
+            )
+        }
+        Data::Union(_) => String::from("null"),
+    };
+
+    Ok(json)
+}
+
+#[cfg(feature = "json-schema")]
+fn fields_json(fields: &[NamedField]) -> String {
+    let fields = fields.iter()
+        .map(|field| {
+            let default = match field.use_default {
+                true => json_string("Default::default()"),
+                false => String::from("null"),
+            };
+
+            let aliases = field.renamed_from.iter()
+                .map(|old| json_string(&old.value()))
+                .collect::<Vec<_>>();
+
+            format!(
+                r#"{{"name":{},"type":{},"required":{},"default":{},"aliases":[{}]}}"#,
+                json_string(&field.key().value()),
+                json_string(&type_name(&field.ty)),
+                !field.use_default,
+                default,
+                aliases.join(","),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    format!("[{}]", fields.join(","))
+}
+
+#[cfg(feature = "json-schema")]
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
+
+fn describe_fields(out: &mut String, fields: &[NamedField], indent: &str) {
+    for field in fields {
+        let requirement = match field.use_default {
+            true => "default: Default::default()",
+            false => "required",
+        };
+
+        let _ = writeln!(out, "{}{}: {} ({})", indent, field.key().value(), type_name(&field.ty), requirement);
+    }
+}
+
+/// Renders a type without the spaces `to_string` puts between tokens.
+fn type_name(ty: &Type) -> String {
+    let name = ty.to_token_stream().to_string();
+    let mut out = String::with_capacity(name.len());
+
+    for (i, c) in name.char_indices() {
+        let next = name[i + c.len_utf8()..].chars().next();
+
+        // keep spaces between words, like in `dyn Trait`, and after commas
+        let joins = out.ends_with(',')
+            || out.ends_with(|c: char| c.is_alphanumeric() || c == '_')
+                && next.map(|c| c.is_alphanumeric() || c == '_').unwrap_or(false);
+
+        if c != ' ' || joins {
+            out.push(c);
+        }
+    }
+
+    out
+}
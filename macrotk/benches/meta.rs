@@ -0,0 +1,95 @@
+//! Benchmarks for parsing meta and the code `#[derive(FromMeta)]` generates.
+//!
+//! Run with `cargo bench -p macrotk`.
+
+#[macro_use]
+extern crate macrotk;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use macrotk::meta::{FromMeta, MetaList, MetaValue};
+use macrotk::syn::parse::Parser;
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+pub struct Opts {
+    pub name: LitStr,
+    #[macrotk(default)]
+    pub threads: u32,
+    #[macrotk(default)]
+    pub ratio: f64,
+    #[macrotk(default)]
+    pub mode: Option<LitStr>,
+    #[macrotk(default)]
+    pub files: Vec<LitStr>,
+    #[macrotk(default)]
+    pub retries: u8,
+    #[macrotk(default)]
+    pub timeout: u64,
+    #[macrotk(default)]
+    pub verbose: Option<u32>,
+}
+
+/// `key0 = 0, key1 = 1, ...`
+fn flat(n: usize) -> String {
+    (0..n).map(|i| format!("key{} = {}", i, i)).collect::<Vec<_>>().join(", ")
+}
+
+/// `a(a(a(... = 1)))`
+fn nested(depth: usize) -> String {
+    format!("{}x = 1{}", "a(".repeat(depth), ")".repeat(depth))
+}
+
+fn parse(c: &mut Criterion) {
+    let flat = flat(100);
+    c.bench_function("parse flat 100", |b| {
+        b.iter(|| MetaList::parse_root_attr.parse_str(black_box(&flat)).unwrap())
+    });
+
+    let nested = nested(64);
+    c.bench_function("parse nested 64", |b| {
+        b.iter(|| MetaList::parse_root_attr.parse_str(black_box(&nested)).unwrap())
+    });
+    c.bench_function("parse nested 64 lazy", |b| {
+        b.iter(|| MetaList::parse_root_attr_lazy.parse_str(black_box(&nested)).unwrap())
+    });
+}
+
+fn get(c: &mut Criterion) {
+    // looking up every key of a big list is quadratic with `get`, but not
+    // through an index
+    let list = MetaList::parse_root_attr.parse_str(&flat(500)).unwrap();
+    let keys = (0..500).map(|i| format!("key{}", i)).collect::<Vec<_>>();
+
+    c.bench_function("get all of 500", |b| {
+        b.iter(|| {
+            for key in keys.iter() {
+                black_box(list.get::<u32>(key).unwrap().unwrap());
+            }
+        })
+    });
+
+    c.bench_function("get all of 500 indexed", |b| {
+        b.iter(|| {
+            let index = list.index();
+
+            for key in keys.iter() {
+                black_box(index.get::<u32>(key).unwrap().unwrap());
+            }
+        })
+    });
+}
+
+fn derive(c: &mut Criterion) {
+    let meta: MetaValue = macrotk::syn::parse_str(
+        r#"opts(name = "x", threads = 8, ratio = 0.5, mode = "fast", files("a", "b"), retries = 3, timeout = 30, verbose = 2)"#,
+    )
+    .unwrap();
+
+    c.bench_function("derive 8 fields", |b| {
+        b.iter(|| Opts::from_meta(black_box(&meta)).unwrap())
+    });
+}
+
+criterion_group!(benches, parse, get, derive);
+criterion_main!(benches);
@@ -3,6 +3,8 @@ pub use macrotk_core::*;
 #[doc(inline)]
 pub use macrotk_derive::*;
 
+pub mod prelude;
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -10,4 +12,229 @@ mod tests {
         let t = trybuild::TestCases::new();
         t.pass("tests/from_meta_derive.rs");
     }
+
+    #[test]
+    pub fn from_meta_generics() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_generics.rs");
+    }
+
+    #[test]
+    pub fn from_meta_enum() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_enum.rs");
+    }
+
+    #[test]
+    pub fn from_meta_untagged() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_untagged.rs");
+    }
+
+    #[test]
+    pub fn from_meta_discriminant() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_discriminant.rs");
+    }
+
+    #[test]
+    pub fn from_meta_schema() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_schema.rs");
+    }
+
+    #[test]
+    #[cfg(feature = "json-schema")]
+    pub fn from_meta_json_schema() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_json_schema.rs");
+    }
+
+    #[test]
+    pub fn from_meta_builder() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_builder.rs");
+    }
+
+    #[test]
+    pub fn from_meta_partial() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_partial.rs");
+    }
+
+    #[test]
+    pub fn from_meta_option() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_option.rs");
+    }
+
+    #[test]
+    pub fn from_meta_recursive() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_recursive.rs");
+    }
+
+    #[test]
+    pub fn from_meta_range() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_range.rs");
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    pub fn from_meta_matches() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_matches.rs");
+    }
+
+    #[test]
+    pub fn from_meta_key_groups() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_key_groups.rs");
+    }
+
+    #[test]
+    pub fn from_meta_conflicts() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_conflicts.rs");
+    }
+
+    #[test]
+    pub fn from_meta_requires() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_requires.rs");
+    }
+
+    #[test]
+    pub fn from_meta_impl_default() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_impl_default.rs");
+    }
+
+    #[test]
+    pub fn from_meta_display() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_display.rs");
+    }
+
+    #[test]
+    pub fn from_meta_attribute() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_attribute.rs");
+    }
+
+    #[test]
+    pub fn from_meta_foreign() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_foreign.rs");
+    }
+
+    #[test]
+    pub fn merge_meta() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/merge_meta.rs");
+    }
+
+    #[test]
+    #[cfg(feature = "config")]
+    pub fn from_meta_config() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_config.rs");
+    }
+
+    #[test]
+    pub fn from_meta_renamed_from() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_renamed_from.rs");
+    }
+
+    #[test]
+    pub fn meta_list_index() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/meta_list_index.rs");
+    }
+
+    #[test]
+    pub fn from_meta_items() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_items.rs");
+    }
+
+    #[test]
+    pub fn from_meta_suffix() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_suffix.rs");
+    }
+
+    #[test]
+    pub fn from_meta_lenient() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_lenient.rs");
+    }
+
+    #[test]
+    pub fn meta_list_recovering() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/meta_list_recovering.rs");
+    }
+
+    #[test]
+    pub fn meta_names() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/meta_names.rs");
+    }
+
+    #[test]
+    pub fn from_meta_lifetime() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_lifetime.rs");
+    }
+
+    #[test]
+    pub fn from_meta_rename() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_rename.rs");
+    }
+
+    #[test]
+    pub fn from_meta_word() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_word.rs");
+    }
+
+    #[test]
+    pub fn parse_meta() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/parse_meta.rs");
+    }
+
+    #[test]
+    pub fn meta_env() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/meta_env.rs");
+    }
+
+    #[test]
+    pub fn from_meta_bool() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/from_meta_bool.rs");
+    }
+
+    #[test]
+    pub fn meta_schema() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/meta_schema.rs");
+    }
+
+    #[test]
+    pub fn apply() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/apply.rs");
+    }
+
+    #[test]
+    pub fn lazy_meta() {
+        let t = trybuild::TestCases::new();
+        t.pass("tests/lazy_meta.rs");
+    }
 }
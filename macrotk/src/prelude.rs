@@ -0,0 +1,21 @@
+//! Common imports for writing macros, as in `use macrotk::prelude::*`.
+
+#[doc(no_inline)]
+pub use macrotk_core::errors::{Context, WithSpan};
+#[doc(no_inline)]
+pub use macrotk_core::merge::MergeMeta;
+#[doc(no_inline)]
+pub use macrotk_core::meta::{FromMeta, Meta, MetaList, MetaValue};
+#[doc(no_inline)]
+pub use macrotk_derive::{parse_meta, FromMeta, MergeMeta};
+
+#[doc(no_inline)]
+pub use macrotk_core::quote::{format_ident, quote, quote_spanned, ToTokens};
+#[doc(no_inline)]
+pub use proc_macro2::{Span, TokenStream};
+#[doc(no_inline)]
+pub use macrotk_core::syn::spanned::Spanned;
+#[doc(no_inline)]
+pub use macrotk_core::syn::{
+    parse_macro_input, Attribute, DeriveInput, Error, Ident, Lit, LitStr, Path,
+};
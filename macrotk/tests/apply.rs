@@ -0,0 +1,40 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::apply::{Apply, Derives, FieldAttrs, Rename};
+use macrotk::quote::{quote, ToTokens};
+use macrotk::syn::{DeriveInput, Error};
+
+#[derive(FromMeta)]
+pub struct Opts {
+    #[macrotk(default)]
+    rename: Option<Rename>,
+    #[macrotk(default)]
+    derives: Option<Derives>,
+    #[macrotk(default)]
+    field_attrs: Option<FieldAttrs>,
+}
+
+impl Apply for Opts {
+    fn apply(&self, item: &mut DeriveInput) -> Result<(), Error> {
+        self.rename.apply(item)?;
+        self.derives.apply(item)?;
+        self.field_attrs.apply(item)
+    }
+}
+
+fn main() {
+    let attr = quote!(rename = "Renamed", derives(Clone, Debug), field_attrs(serde(default)));
+    let item = quote!(struct Original { a: u8 });
+
+    let output = macrotk::apply::apply::<Opts>(attr, item).unwrap();
+    let expected: DeriveInput = macrotk::syn::parse_quote! {
+        #[derive(Clone, Debug)]
+        struct Renamed {
+            #[serde(default)]
+            a: u8
+        }
+    };
+
+    assert_eq!(output.to_string(), expected.into_token_stream().to_string());
+}
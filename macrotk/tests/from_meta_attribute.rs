@@ -0,0 +1,26 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::syn::{DeriveInput, LitStr};
+
+#[derive(FromMeta)]
+#[macrotk(attribute = "sqlx", attribute = "db")]
+pub struct Opts {
+    table: LitStr,
+    #[macrotk(default)]
+    schema: Option<LitStr>,
+}
+
+fn main() {
+    let item: DeriveInput = macrotk::syn::parse_quote! {
+        #[sqlx(table = "users")]
+        #[serde(rename_all = "camelCase")]
+        #[db(schema = "public")]
+        struct User;
+    };
+
+    let opts = Opts::from_attributes(&item.attrs).unwrap();
+    assert_eq!(Opts::ATTRIBUTES, ["sqlx", "db"]);
+    assert_eq!(opts.table.value(), "users");
+    assert_eq!(opts.schema.unwrap().value(), "public");
+}
@@ -0,0 +1,24 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::Meta;
+
+#[derive(FromMeta)]
+pub struct Opts {
+    #[macrotk(default)]
+    flag: bool,
+}
+
+fn main() {
+    let opts: Meta<Opts> = macrotk::syn::parse_str("flag").unwrap();
+    assert!(opts.flag);
+
+    let opts: Meta<Opts> = macrotk::syn::parse_str("flag = false").unwrap();
+    assert!(!opts.flag);
+
+    let opts: Meta<Opts> = macrotk::syn::parse_str("").unwrap();
+    assert!(!opts.flag);
+
+    let err = macrotk::syn::parse_str::<Meta<Opts>>("flag = none").err().unwrap();
+    assert_eq!(err.to_string(), "`none` is only valid for Option fields");
+}
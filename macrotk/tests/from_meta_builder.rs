@@ -0,0 +1,69 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::MetaValue;
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+#[macrotk(builder)]
+pub struct Opts {
+    name: LitStr,
+    #[macrotk(default)]
+    mode: Option<LitStr>,
+}
+
+#[derive(FromMeta)]
+#[macrotk(builder)]
+pub struct Wrapper<T> {
+    inner: T,
+}
+
+#[derive(FromMeta)]
+#[macrotk(builder, word = "path", at_least_one_of(path, inline))]
+pub struct Source {
+    #[macrotk(default, conflicts_with = "inline")]
+    path: Option<LitStr>,
+    #[macrotk(default)]
+    inline: Option<LitStr>,
+}
+
+fn main() {
+    let defaults: MetaValue = macrotk::syn::parse_str(r#"opts(name = "default", mode = "fast")"#).unwrap();
+    let attr: MetaValue = macrotk::syn::parse_str(r#"opts(name = "custom")"#).unwrap();
+
+    let mut builder = Opts::builder();
+    builder.merge(&defaults).unwrap();
+    builder.merge(&attr).unwrap();
+    let opts = builder.build().unwrap();
+
+    assert_eq!(opts.name.value(), "custom");
+    assert_eq!(opts.mode.unwrap().value(), "fast");
+
+    let mut wrapper = Wrapper::<LitStr>::builder();
+    wrapper.inner = Some(LitStr::new("set", macrotk::Span::call_site()));
+    assert_eq!(wrapper.build().unwrap().inner.value(), "set");
+
+    let err = OptsBuilder::default().build().err().unwrap();
+    assert_eq!(err.to_string(), "missing value for name");
+
+    // builders check the same relations between keys as from_meta
+    let a: MetaValue = macrotk::syn::parse_str(r#"source(path = "a")"#).unwrap();
+    let b: MetaValue = macrotk::syn::parse_str(r#"source(inline = "b")"#).unwrap();
+
+    let mut source = Source::builder();
+    source.merge(&a).unwrap();
+    source.merge(&b).unwrap();
+    let err = source.build().err().unwrap();
+    assert_eq!(err.to_string(), "`path` and `inline` can't both be given");
+
+    let err = Source::builder().build().err().unwrap();
+    assert_eq!(err.to_string(), "expected at least one of `path`, `inline`");
+
+    let both: MetaValue = macrotk::syn::parse_str(r#"source(path = "a", inline = "b")"#).unwrap();
+    assert!(Source::from_meta_partial(&both).into_result().unwrap().build().is_err());
+
+    // and take the word form
+    let word: MetaValue = macrotk::syn::parse_str(r#"source("a")"#).unwrap();
+    let source = Source::from_meta_partial(&word).into_result().unwrap().build().unwrap();
+    assert_eq!(source.path.unwrap().value(), "a");
+}
@@ -0,0 +1,20 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::Meta;
+
+#[derive(FromMeta)]
+#[macrotk(config = "macrotk.toml")]
+pub struct Opts {
+    #[macrotk(default)]
+    threads: u32,
+}
+
+fn main() {
+    // there is no config file here, so only the attribute is read
+    let opts: Meta<Opts> = macrotk::syn::parse_str("threads = 8").unwrap();
+    assert_eq!(opts.threads, 8);
+
+    let opts: Meta<Opts> = macrotk::syn::parse_str("").unwrap();
+    assert_eq!(opts.threads, 0);
+}
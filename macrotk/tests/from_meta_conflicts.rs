@@ -0,0 +1,25 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::Meta;
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+pub struct Source {
+    #[macrotk(default, conflicts_with = "inline")]
+    path: Option<LitStr>,
+    #[macrotk(default)]
+    inline: Option<LitStr>,
+}
+
+fn main() {
+    let source: Meta<Source> = macrotk::syn::parse_str(r#"path = "x""#).unwrap();
+    assert!(source.path.is_some());
+
+    let err = macrotk::syn::parse_str::<Meta<Source>>(r#"path = "a", inline = "b""#).err().unwrap();
+    let messages = err.into_iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(messages, [
+        "`path` and `inline` can't both be given",
+        "`path` and `inline` can't both be given",
+    ]);
+}
@@ -0,0 +1,30 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::Meta;
+
+#[derive(FromMeta, Debug, PartialEq)]
+pub enum Level {
+    Trace = 0,
+    Info = 2,
+    Error = 3,
+}
+
+#[derive(FromMeta)]
+pub struct Opts {
+    level: Level,
+}
+
+fn main() {
+    let opts: Meta<Opts> = macrotk::syn::parse_str("level = 3").unwrap();
+    assert_eq!(opts.level, Level::Error);
+
+    let opts: Meta<Opts> = macrotk::syn::parse_str(r#"level = "info""#).unwrap();
+    assert_eq!(opts.level, Level::Info);
+
+    let opts: Meta<Opts> = macrotk::syn::parse_str("level(trace)").unwrap();
+    assert_eq!(opts.level, Level::Trace);
+
+    let err = macrotk::syn::parse_str::<Meta<Opts>>("level = 1").err().unwrap();
+    assert_eq!(err.to_string(), "no variant has the discriminant 1");
+}
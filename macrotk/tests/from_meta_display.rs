@@ -0,0 +1,38 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::Meta;
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+#[macrotk(display)]
+pub enum Codec {
+    Json {
+        #[macrotk(default)]
+        style: Option<LitStr>,
+    },
+    Raw,
+}
+
+#[derive(FromMeta)]
+#[macrotk(display)]
+pub struct Opts {
+    name: LitStr,
+    threads: u32,
+    #[macrotk(default)]
+    ratio: Option<f64>,
+    codec: Codec,
+}
+
+fn main() {
+    let source = r#"name = "x", threads = 8, codec(json(style = "pretty"))"#;
+    let opts: Meta<Opts> = macrotk::syn::parse_str(source).unwrap();
+
+    // the canonical form parses back to the same thing
+    assert_eq!(opts.to_string(), source);
+    let again: Meta<Opts> = macrotk::syn::parse_str(&opts.to_string()).unwrap();
+    assert_eq!(again.to_string(), source);
+
+    let opts: Meta<Opts> = macrotk::syn::parse_str(r#"name = "y", threads = 1, ratio = 0.5, codec(raw)"#).unwrap();
+    assert_eq!(opts.to_string(), r#"name = "y", threads = 1, ratio = 0.5, codec(raw)"#);
+}
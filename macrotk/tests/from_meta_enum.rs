@@ -0,0 +1,36 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::Meta;
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+pub enum Codec {
+    Json {
+        #[macrotk(default)]
+        style: Option<LitStr>,
+    },
+    Bincode {
+        limit: LitStr,
+    },
+    Raw,
+}
+
+#[derive(FromMeta)]
+pub struct Opts {
+    codec: Codec,
+}
+
+fn main() {
+    let opts: Meta<Opts> = macrotk::syn::parse_str(r#"codec(json(style = "pretty"))"#).unwrap();
+    assert!(matches!(opts.codec, Codec::Json { style: Some(ref s) } if s.value() == "pretty"));
+
+    let codec: Meta<Codec> = macrotk::syn::parse_str(r#"bincode(limit = "1k")"#).unwrap();
+    assert!(matches!(*codec, Codec::Bincode { ref limit } if limit.value() == "1k"));
+
+    let codec: Meta<Codec> = macrotk::syn::parse_str("raw").unwrap();
+    assert!(matches!(*codec, Codec::Raw));
+
+    let err = macrotk::syn::parse_str::<Meta<Codec>>("yaml").err().unwrap();
+    assert_eq!(err.to_string(), "unknown variant `yaml`, expected one of: json, bincode, raw");
+}
@@ -0,0 +1,44 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::syn::{DeriveInput, LitStr};
+
+#[derive(FromMeta)]
+pub struct SerdeOpts {
+    #[macrotk(default)]
+    rename: Option<LitStr>,
+}
+
+#[derive(FromMeta, Debug, PartialEq)]
+pub enum Repr {
+    U8,
+    U32,
+}
+
+#[derive(FromMeta)]
+#[macrotk(attribute = "table")]
+pub struct Opts {
+    name: LitStr,
+    #[macrotk(foreign = "serde")]
+    serde: SerdeOpts,
+    #[macrotk(default, foreign = "repr")]
+    repr: Option<Repr>,
+    #[macrotk(default, foreign = "doc")]
+    doc: Option<LitStr>,
+}
+
+fn main() {
+    let item: DeriveInput = macrotk::syn::parse_quote! {
+        /// Users.
+        #[table(name = "users")]
+        #[serde(rename = "User")]
+        #[repr(u8)]
+        enum User { A }
+    };
+
+    let opts = Opts::from_attributes(&item.attrs).unwrap();
+    assert_eq!(opts.name.value(), "users");
+    assert_eq!(opts.serde.rename.unwrap().value(), "User");
+    assert_eq!(opts.repr, Some(Repr::U8));
+    assert_eq!(opts.doc.unwrap().value(), " Users.");
+}
@@ -0,0 +1,16 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::Meta;
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+pub struct Test<T> {
+    help: T,
+}
+
+fn main() {
+    let meta: Meta<Test<LitStr>> = macrotk::syn::parse_str("help = \"text\"").unwrap();
+
+    assert_eq!(meta.help.value(), "text");
+}
@@ -0,0 +1,19 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::Meta;
+
+#[derive(FromMeta, Debug, PartialEq)]
+#[macrotk(impl_default)]
+pub struct Opts {
+    #[macrotk(default)]
+    threads: u32,
+    #[macrotk(default)]
+    ratio: Option<f64>,
+}
+
+fn main() {
+    let opts: Meta<Opts> = macrotk::syn::parse_str("").unwrap();
+
+    assert_eq!(*opts, Opts::default());
+}
@@ -0,0 +1,31 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::Meta;
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+pub struct Opts {
+    #[macrotk(min_items = 1, max_items = 3, unique)]
+    files: Vec<LitStr>,
+    #[macrotk(default)]
+    ports: Vec<u16>,
+}
+
+fn main() {
+    let opts: Meta<Opts> = macrotk::syn::parse_str(r#"files("a", "b"), ports(80, 443)"#).unwrap();
+    assert_eq!(opts.files.len(), 2);
+    assert_eq!(opts.ports, [80, 443]);
+
+    let err = macrotk::syn::parse_str::<Meta<Opts>>("files()").err().unwrap();
+    assert_eq!(err.to_string(), "expected at least 1 item");
+
+    let err = macrotk::syn::parse_str::<Meta<Opts>>(r#"files("a", "b", "c", "d")"#).err().unwrap();
+    assert_eq!(err.to_string(), "expected at most 3 items");
+
+    let err = macrotk::syn::parse_str::<Meta<Opts>>(r#"files("a", "b", "a")"#).err().unwrap();
+    assert_eq!(err.to_string(), "duplicate item");
+
+    let err = macrotk::syn::parse_str::<Meta<Opts>>(r#"files("a"), ports(80, "x")"#).err().unwrap();
+    assert_eq!(err.to_string(), "expected integer literal");
+}
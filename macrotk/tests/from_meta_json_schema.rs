@@ -0,0 +1,24 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+#[macrotk(schema)]
+pub struct Opts {
+    name: LitStr,
+    #[macrotk(default, renamed_from = "kind")]
+    mode: Option<LitStr>,
+}
+
+fn main() {
+    assert_eq!(
+        Opts::JSON_SCHEMA,
+        concat!(
+            r#"{"kind":"struct","fields":["#,
+            r#"{"name":"name","type":"LitStr","required":true,"default":null,"aliases":[]},"#,
+            r#"{"name":"mode","type":"Option<LitStr>","required":false,"default":"Default::default()","aliases":["kind"]}"#,
+            r#"]}"#,
+        ),
+    );
+}
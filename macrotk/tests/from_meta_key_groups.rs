@@ -0,0 +1,42 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::Meta;
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+#[macrotk(exactly_one_of(path, url, inline))]
+pub struct Source {
+    #[macrotk(default)]
+    path: Option<LitStr>,
+    #[macrotk(default)]
+    url: Option<LitStr>,
+    #[macrotk(default)]
+    inline: Option<LitStr>,
+}
+
+#[derive(FromMeta)]
+#[macrotk(at_least_one_of(read, write))]
+pub struct Access {
+    #[macrotk(default)]
+    read: Option<LitStr>,
+    #[macrotk(default)]
+    write: Option<LitStr>,
+}
+
+fn main() {
+    let source: Meta<Source> = macrotk::syn::parse_str(r#"url = "x""#).unwrap();
+    assert_eq!(source.url.as_ref().unwrap().value(), "x");
+
+    let err = macrotk::syn::parse_str::<Meta<Source>>("").err().unwrap();
+    assert_eq!(err.to_string(), "expected one of `path`, `url`, `inline`");
+
+    let err = macrotk::syn::parse_str::<Meta<Source>>(r#"path = "a", inline = "b""#).err().unwrap();
+    assert_eq!(err.into_iter().count(), 2);
+
+    let access: Meta<Access> = macrotk::syn::parse_str(r#"read = "a", write = "b""#).unwrap();
+    assert!(access.read.is_some() && access.write.is_some());
+
+    let err = macrotk::syn::parse_str::<Meta<Access>>("").err().unwrap();
+    assert_eq!(err.to_string(), "expected at least one of `read`, `write`");
+}
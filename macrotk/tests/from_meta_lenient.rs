@@ -0,0 +1,42 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::{Meta, MetaValue};
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+#[macrotk(lenient)]
+pub struct Opts {
+    name: LitStr,
+    #[macrotk(default, range = "1..=64")]
+    threads: u32,
+}
+
+#[derive(FromMeta)]
+pub struct Strict {
+    #[allow(dead_code)]
+    name: LitStr,
+}
+
+fn main() {
+    // `other` is for another tool sharing the attribute
+    let opts: Meta<Opts> = macrotk::syn::parse_str(r#"name = "x", other = 1, threads = "8""#).unwrap();
+    assert_eq!(opts.name.value(), "x");
+    assert_eq!(opts.threads, 0);
+
+    // what was tolerated isn't warned about
+    assert!(macrotk::warnings::take().is_empty());
+
+    let meta: MetaValue = macrotk::syn::parse_str(r#"opts(name = "x", other = 1, threads = "8")"#).unwrap();
+    let partial = Opts::from_meta_lenient(&meta).unwrap();
+    let errors = partial.errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+    assert_eq!(errors, ["expected integer literal", "unknown key `other`"]);
+
+    // fields without defaults still have to parse
+    let err = macrotk::syn::parse_str::<Meta<Opts>>("name = 1").err().unwrap();
+    assert_eq!(err.to_string(), "expected str literal");
+
+    // without `lenient`, unknown keys are errors
+    let err = macrotk::syn::parse_str::<Meta<Strict>>(r#"name = "x", other = 1"#).err().unwrap();
+    assert_eq!(err.to_string(), "unknown key `other`");
+}
@@ -0,0 +1,36 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::Meta;
+use macrotk::syn::Lifetime;
+
+#[derive(FromMeta)]
+#[macrotk(display)]
+pub struct Opts {
+    lifetime: Lifetime,
+    #[macrotk(default)]
+    bounds: Vec<Lifetime>,
+}
+
+fn main() {
+    let opts: Meta<Opts> = macrotk::syn::parse_str("lifetime = 'de, bounds('a, 'b)").unwrap();
+    assert_eq!(opts.lifetime.ident, "de");
+    assert_eq!(opts.bounds.len(), 2);
+    assert_eq!(opts.to_string(), "lifetime = 'de, bounds('a, 'b)");
+
+    let opts: Meta<Opts> = macrotk::syn::parse_str(r#"lifetime = "'de""#).unwrap();
+    assert_eq!(opts.lifetime.ident, "de");
+
+    let err = macrotk::syn::parse_str::<Meta<Opts>>(r#"lifetime = "de""#).err().unwrap();
+    assert_eq!(err.to_string(), "expected a lifetime; add a tick, like `'de`");
+
+    let err = macrotk::syn::parse_str::<Meta<Opts>>("lifetime = 'a, bounds(b)").err().unwrap();
+    assert_eq!(err.to_string(), "expected a lifetime; add a tick, like `'b`");
+
+    // a lone identifier isn't a value, but the error still shows the tick
+    let err = macrotk::syn::parse_str::<Meta<Opts>>("lifetime = de").err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        r#"expected a literal, a lifetime, `none` or env(...), like `"de"` or `'de`"#,
+    );
+}
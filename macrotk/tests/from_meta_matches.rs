@@ -0,0 +1,19 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::Meta;
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+pub struct Opts {
+    #[macrotk(matches = "^[A-Za-z_][A-Za-z0-9_]*$")]
+    name: LitStr,
+}
+
+fn main() {
+    let opts: Meta<Opts> = macrotk::syn::parse_str(r#"name = "snake_case""#).unwrap();
+    assert_eq!(opts.name.value(), "snake_case");
+
+    let err = macrotk::syn::parse_str::<Meta<Opts>>(r#"name = "kebab-case""#).err().unwrap();
+    assert_eq!(err.to_string(), "expected a value matching `^[A-Za-z_][A-Za-z0-9_]*$`");
+}
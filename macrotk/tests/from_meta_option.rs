@@ -0,0 +1,29 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::Meta;
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+pub struct Opts {
+    #[macrotk(default)]
+    a: Option<LitStr>,
+    #[macrotk(default)]
+    b: Option<LitStr>,
+    #[macrotk(default)]
+    c: Option<LitStr>,
+}
+
+fn main() {
+    let opts: Meta<Opts> = macrotk::syn::parse_str(r#"a = "x", b = none"#).unwrap();
+
+    assert_eq!(opts.a.as_ref().unwrap().value(), "x");
+    assert!(opts.b.is_none());
+    assert!(opts.c.is_none());
+
+    let err = macrotk::syn::parse_str::<Meta<Opts>>("a = nothing").err().unwrap();
+    assert_eq!(err.to_string(), r#"expected a literal, a lifetime, `none` or env(...), like `"nothing"` or `'nothing`"#);
+
+    // booleans are still literals
+    assert!(macrotk::syn::parse_str::<macrotk::meta::MetaValue>("a = true").unwrap().value().literal().is_ok());
+}
@@ -0,0 +1,27 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::MetaValue;
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+#[macrotk(builder)]
+pub struct Opts {
+    name: LitStr,
+    mode: LitStr,
+}
+
+fn main() {
+    let meta: MetaValue = macrotk::syn::parse_str(r#"opts(name = "x", mode(fast))"#).unwrap();
+
+    let partial = Opts::from_meta_partial(&meta);
+    assert!(!partial.is_ok());
+    assert_eq!(partial.errors.len(), 1);
+    assert_eq!(partial.value.name.as_ref().unwrap().value(), "x");
+    assert!(partial.value.mode.is_none());
+
+    let meta: MetaValue = macrotk::syn::parse_str(r#"opts(name = "x", mode = "fast")"#).unwrap();
+
+    let opts = Opts::from_meta_partial(&meta).into_result().unwrap().build().unwrap();
+    assert_eq!(opts.mode.value(), "fast");
+}
@@ -0,0 +1,32 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::Meta;
+
+#[derive(FromMeta)]
+#[macrotk(builder)]
+pub struct Opts {
+    #[macrotk(range = "1..=64")]
+    threads: u32,
+    #[macrotk(default, range = "0.0..1.0")]
+    ratio: f64,
+}
+
+fn main() {
+    let opts: Meta<Opts> = macrotk::syn::parse_str("threads = 8, ratio = 0.5").unwrap();
+    assert_eq!(opts.threads, 8);
+    assert_eq!(opts.ratio, 0.5);
+
+    // defaults aren't checked
+    let opts: Meta<Opts> = macrotk::syn::parse_str("threads = 64").unwrap();
+    assert_eq!(opts.ratio, 0.0);
+
+    let err = macrotk::syn::parse_str::<Meta<Opts>>("threads = 65").err().unwrap();
+    assert_eq!(err.to_string(), "expected a value in the range 1..=64");
+
+    let err = macrotk::syn::parse_str::<Meta<Opts>>("threads = 1, ratio = 1.0").err().unwrap();
+    assert_eq!(err.to_string(), "expected a value in the range 0.0..1.0");
+
+    let meta = macrotk::syn::parse_str("opts(threads = 0)").unwrap();
+    assert_eq!(Opts::from_meta_partial(&meta).errors.len(), 1);
+}
@@ -0,0 +1,37 @@
+#[macro_use]
+extern crate macrotk;
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+use macrotk::meta::Meta;
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+pub struct Node {
+    name: LitStr,
+    #[macrotk(default)]
+    child: Option<Box<Node>>,
+}
+
+#[derive(FromMeta)]
+pub struct Shared {
+    rc: Rc<LitStr>,
+    arc: Arc<LitStr>,
+}
+
+fn main() {
+    let node: Meta<Node> = macrotk::syn::parse_str(
+        r#"name = "a", child(name = "b", child(name = "c"))"#,
+    ).unwrap();
+
+    let b = node.child.as_ref().unwrap();
+    let c = b.child.as_ref().unwrap();
+    assert_eq!(b.name.value(), "b");
+    assert_eq!(c.name.value(), "c");
+    assert!(c.child.is_none());
+
+    let shared: Meta<Shared> = macrotk::syn::parse_str(r#"rc = "x", arc = "y""#).unwrap();
+    assert_eq!(shared.rc.value(), "x");
+    assert_eq!(shared.arc.value(), "y");
+}
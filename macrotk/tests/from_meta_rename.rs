@@ -0,0 +1,30 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::{Meta, MetaList};
+use macrotk::syn::LitStr;
+use macrotk::syn::parse::Parser;
+
+#[derive(FromMeta)]
+#[macrotk(display, lenient)]
+pub struct Opts {
+    #[macrotk(rename = "x-amz-header")]
+    header: LitStr,
+    #[macrotk(default, rename = "type")]
+    kind: Option<LitStr>,
+}
+
+fn main() {
+    let list = MetaList::parse_root_attr_lazy.parse_str(r#""x-amz-header" = "v""#).unwrap();
+    assert_eq!(list.get::<LitStr>("x-amz-header").unwrap().unwrap().value(), "v");
+
+    // keywords aren't identifiers either
+    let opts: Meta<Opts> = macrotk::syn::parse_str(r#""x-amz-header" = "v", "type" = "a""#).unwrap();
+    assert_eq!(opts.header.value(), "v");
+    assert_eq!(opts.kind.as_ref().unwrap().value(), "a");
+    assert_eq!(opts.to_string(), r#""x-amz-header" = "v", "type" = "a""#);
+
+    let meta = macrotk::syn::parse_str(r#"opts("x-amz-header" = "v", "x-other" = 1)"#).unwrap();
+    let errors = Opts::from_meta_lenient(&meta).unwrap().errors;
+    assert_eq!(errors[0].to_string(), "unknown key `x-other`");
+}
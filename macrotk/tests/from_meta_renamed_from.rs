@@ -0,0 +1,24 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::Meta;
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+pub struct Opts {
+    #[macrotk(renamed_from = "file")]
+    path: LitStr,
+}
+
+fn main() {
+    let opts: Meta<Opts> = macrotk::syn::parse_str("path = \"a.rs\"").unwrap();
+    assert_eq!(opts.path.value(), "a.rs");
+    assert!(macrotk::warnings::take().is_empty());
+
+    let opts: Meta<Opts> = macrotk::syn::parse_str("file = \"b.rs\"").unwrap();
+    assert_eq!(opts.path.value(), "b.rs");
+
+    let warnings = macrotk::warnings::take().to_string();
+    assert!(warnings.contains("deprecated"));
+    assert!(warnings.contains("`file` is renamed to `path`"));
+}
@@ -0,0 +1,24 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::Meta;
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+pub struct Tls {
+    #[macrotk(default, requires = "cert")]
+    key: Option<LitStr>,
+    #[macrotk(default)]
+    cert: Option<LitStr>,
+}
+
+fn main() {
+    let tls: Meta<Tls> = macrotk::syn::parse_str(r#"key = "a", cert = "b""#).unwrap();
+    assert!(tls.key.is_some());
+
+    let tls: Meta<Tls> = macrotk::syn::parse_str(r#"cert = "b""#).unwrap();
+    assert!(tls.key.is_none());
+
+    let err = macrotk::syn::parse_str::<Meta<Tls>>(r#"key = "a""#).err().unwrap();
+    assert_eq!(err.to_string(), "`key` requires `cert`");
+}
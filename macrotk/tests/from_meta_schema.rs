@@ -0,0 +1,34 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+#[macrotk(schema)]
+pub struct Opts {
+    name: LitStr,
+    #[macrotk(default)]
+    mode: Option<LitStr>,
+}
+
+#[derive(FromMeta)]
+#[macrotk(schema)]
+pub enum Codec {
+    Json {
+        #[macrotk(default)]
+        style: Option<LitStr>,
+    },
+    Raw,
+}
+
+fn main() {
+    assert_eq!(
+        Opts::SCHEMA,
+        "name: LitStr (required)\nmode: Option<LitStr> (default: Default::default())",
+    );
+
+    assert_eq!(
+        Codec::SCHEMA,
+        "one of:\n  json(...)\n    style: Option<LitStr> (default: Default::default())\n  raw",
+    );
+}
@@ -0,0 +1,28 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::Meta;
+
+#[derive(FromMeta)]
+pub struct Opts {
+    size: usize,
+    mask: u8,
+    #[macrotk(default)]
+    ratio: f32,
+}
+
+fn main() {
+    let opts: Meta<Opts> = macrotk::syn::parse_str("size = 10usize, mask = 0xFFu8, ratio = 0.5f32").unwrap();
+    assert_eq!(opts.size, 10);
+    assert_eq!(opts.mask, 0xFF);
+    assert_eq!(opts.ratio, 0.5);
+
+    let err = macrotk::syn::parse_str::<Meta<Opts>>("size = 10u32, mask = 0").err().unwrap();
+    assert_eq!(err.to_string(), "expected a `usize` literal, found a `u32` literal");
+
+    let err = macrotk::syn::parse_str::<Meta<Opts>>("size = 1, mask = 256").err().unwrap();
+    assert_eq!(err.to_string(), "value out of range for `u8`");
+
+    let err = macrotk::syn::parse_str::<Meta<Opts>>("size = 1, mask = 0, ratio = 1f64").err().unwrap();
+    assert_eq!(err.to_string(), "expected a `f32` literal, found a `f64` literal");
+}
@@ -0,0 +1,43 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::Meta;
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+pub struct Config {
+    path: LitStr,
+    #[macrotk(default)]
+    mode: Option<LitStr>,
+}
+
+#[derive(FromMeta)]
+#[macrotk(untagged)]
+pub enum Source {
+    Disabled,
+    Short(LitStr),
+    Full {
+        config: Config,
+    },
+}
+
+#[derive(FromMeta)]
+pub struct Opts {
+    source: Source,
+}
+
+fn main() {
+    let opts: Meta<Opts> = macrotk::syn::parse_str(r#"source = "a.toml""#).unwrap();
+    assert!(matches!(opts.source, Source::Short(ref s) if s.value() == "a.toml"));
+
+    let opts: Meta<Opts> = macrotk::syn::parse_str(r#"source(config(path = "b.toml"))"#).unwrap();
+    assert!(matches!(opts.source, Source::Full { ref config } if config.path.value() == "b.toml"));
+
+    let opts: Meta<Opts> = macrotk::syn::parse_str("source(disabled)").unwrap();
+    assert!(matches!(opts.source, Source::Disabled));
+
+    let err = macrotk::syn::parse_str::<Meta<Opts>>("source(other)").err().unwrap();
+    let messages = err.into_iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(messages.len(), 4);
+    assert_eq!(messages[0], "value did not match any variant");
+}
@@ -0,0 +1,33 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::{FromMeta, Meta, MetaValue};
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+#[macrotk(word = "name")]
+pub struct Table {
+    name: LitStr,
+    #[macrotk(default)]
+    schema: Option<LitStr>,
+}
+
+fn main() {
+    let table: Meta<Table> = macrotk::syn::parse_str(r#""users""#).unwrap();
+    assert_eq!(table.name.value(), "users");
+
+    let table: Meta<Table> = macrotk::syn::parse_str("users").unwrap();
+    assert_eq!(table.name.value(), "users");
+
+    let table: Meta<Table> = macrotk::syn::parse_str(r#"name = "users", schema = "public""#).unwrap();
+    assert_eq!(table.name.value(), "users");
+    assert_eq!(table.schema.as_ref().unwrap().value(), "public");
+
+    // nested values have a word form too
+    let meta: MetaValue = macrotk::syn::parse_str("table(users)").unwrap();
+    assert_eq!(Table::from_meta(&meta).unwrap().name.value(), "users");
+
+    // a bare key is still a key
+    let err = macrotk::syn::parse_str::<Meta<Table>>("schema").err().unwrap();
+    assert_eq!(err.to_string(), "missing value for name");
+}
@@ -0,0 +1,34 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::meta::{Meta, MetaList};
+use macrotk::syn::LitStr;
+use macrotk::syn::parse::Parser;
+
+#[derive(FromMeta)]
+pub struct Inner {
+    name: LitStr,
+}
+
+#[derive(FromMeta)]
+pub struct Opts {
+    inner: Inner,
+}
+
+fn main() {
+    // `unused` is never looked at, so the bad tokens inside never error
+    let list = MetaList::parse_root_attr_lazy
+        .parse_str(r#"inner(name = "x"), unused(= = =)"#)
+        .unwrap();
+    let inner = list.get::<Inner>("inner").unwrap().unwrap();
+
+    assert_eq!(inner.name.value(), "x");
+    assert!(list.find("unused").unwrap().list().is_err());
+
+    let err = Meta::<Opts>::parse_lazy
+        .parse_str(r#"inner(name = = "x")"#)
+        .err()
+        .unwrap();
+
+    assert_eq!(err.to_string(), "expected literal");
+}
@@ -0,0 +1,31 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::merge::MergeMeta;
+use macrotk::meta::Meta;
+
+#[derive(FromMeta, MergeMeta)]
+pub struct Opts {
+    #[macrotk(default)]
+    threads: u32,
+    #[macrotk(default)]
+    name: Option<macrotk::syn::LitStr>,
+    #[macrotk(default)]
+    verbose: bool,
+    #[macrotk(default)]
+    retries: Option<u32>,
+}
+
+fn main() {
+    let base: Meta<Opts> = macrotk::syn::parse_str("threads = 8, name = \"base\", verbose, retries = 3").unwrap();
+    let item: Meta<Opts> = macrotk::syn::parse_str("threads = 0, name = \"item\", retries = 0").unwrap();
+
+    let opts = base.into_inner().merge(item.into_inner());
+
+    assert_eq!(opts.threads, 8);
+    assert_eq!(opts.name.unwrap().value(), "item");
+    assert!(opts.verbose);
+
+    // zero is unset, so it doesn't override
+    assert_eq!(opts.retries, Some(3));
+}
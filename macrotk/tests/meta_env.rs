@@ -0,0 +1,25 @@
+extern crate macrotk;
+
+use macrotk::meta::MetaList;
+use macrotk::syn::parse::Parser;
+use macrotk::syn::LitStr;
+
+fn main() {
+    std::env::set_var("MACROTK_TEST_NAME", "users");
+    let list = MetaList::parse_root_attr.parse_str(r#"name = env("MACROTK_TEST_NAME")"#).unwrap();
+    assert_eq!(list.get::<LitStr>("name").unwrap().unwrap().value(), "users");
+
+    std::env::remove_var("MACROTK_TEST_NAME");
+    let err = MetaList::parse_root_attr.parse_str(r#"name = env("MACROTK_TEST_NAME")"#).err().unwrap();
+    assert_eq!(err.to_string(), "environment variable `MACROTK_TEST_NAME` is not set");
+
+    #[cfg(unix)]
+    {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        std::env::set_var("MACROTK_TEST_NAME", OsStr::from_bytes(b"\xff"));
+        let err = MetaList::parse_root_attr.parse_str(r#"name = env("MACROTK_TEST_NAME")"#).err().unwrap();
+        assert_eq!(err.to_string(), "environment variable `MACROTK_TEST_NAME` is not valid unicode");
+    }
+}
@@ -0,0 +1,23 @@
+extern crate macrotk;
+
+use macrotk::meta::MetaList;
+use macrotk::syn::parse::Parser;
+
+fn main() {
+    let list = MetaList::parse_root_attr.parse_str("1, 2, 3").unwrap();
+
+    assert_eq!(list.first::<u32>().unwrap().unwrap(), 1);
+    assert_eq!(list.get_index::<u32>(1).unwrap().unwrap(), 2);
+    assert_eq!(list.last::<u32>().unwrap().unwrap(), 3);
+    assert!(list.get_index::<u32>(3).is_none());
+
+    let empty = MetaList::parse_root_attr.parse_str("").unwrap();
+    assert!(empty.first::<u32>().is_none());
+
+    // an index finds keys like `get`, including string keys
+    let list = MetaList::parse_root_attr.parse_str(r#"a = 1, "b-c" = 2, a = 3"#).unwrap();
+    let index = list.index();
+    assert_eq!(index.get::<u32>("a").unwrap().unwrap(), 1);
+    assert_eq!(index.get::<u32>("b-c").unwrap().unwrap(), 2);
+    assert!(index.find("d").is_none());
+}
@@ -0,0 +1,26 @@
+extern crate macrotk;
+
+use macrotk::meta::MetaList;
+use macrotk::syn::parse::Parser;
+
+fn main() {
+    let partial = MetaList::parse_root_attr_recovering
+        .parse_str(r#"name = "x", threads = = 8, mode(fast), , ratio = bad, last"#)
+        .unwrap();
+
+    let names = partial.value.list.iter()
+        .map(|entry| entry.name().unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["name", "mode", "last"]);
+
+    let errors = partial.errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+    assert_eq!(errors, [
+        "expected literal",
+        "expected an entry before this comma",
+        r#"expected a literal, a lifetime, `none` or env(...), like `"bad"` or `'bad`"#,
+    ]);
+
+    let partial = MetaList::parse_root_attr_recovering.parse_str("a, b,").unwrap();
+    assert!(partial.is_ok());
+    assert_eq!(partial.value.list.len(), 2);
+}
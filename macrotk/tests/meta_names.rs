@@ -0,0 +1,17 @@
+extern crate macrotk;
+
+use macrotk::meta::MetaList;
+use macrotk::syn::parse::Parser;
+
+fn main() {
+    let list = MetaList::parse_root_attr.parse_str(r#"color = "red", "x""#).unwrap();
+    let color = &list.list[0];
+
+    assert!(color.has_name("color"));
+    assert!(!color.has_name("colour"));
+    assert!(color.has_any_name(&["colour", "color"]));
+    assert_eq!(color.name().unwrap().to_string(), "color");
+
+    // literals have no name
+    assert!(!list.list[1].has_any_name(&["x"]));
+}
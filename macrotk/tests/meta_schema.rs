@@ -0,0 +1,31 @@
+#[macro_use]
+extern crate macrotk;
+
+use macrotk::schema::{FieldKind, FieldSpec, MetaSchema};
+use macrotk::syn::LitStr;
+
+#[derive(FromMeta)]
+pub struct Opts {
+    name: LitStr,
+    #[macrotk(default)]
+    mode: Option<LitStr>,
+}
+
+#[derive(FromMeta)]
+pub enum Codec {
+    Json,
+    Raw,
+}
+
+fn main() {
+    assert_eq!(
+        Opts::fields(),
+        &[
+            FieldSpec { name: "name", required: true, kind: FieldKind::Field("LitStr") },
+            FieldSpec { name: "mode", required: false, kind: FieldKind::Field("Option<LitStr>") },
+        ],
+    );
+
+    let names = Codec::fields().iter().map(|f| f.name).collect::<Vec<_>>();
+    assert_eq!(names, ["json", "raw"]);
+}
@@ -0,0 +1,24 @@
+use macrotk::prelude::*;
+
+fn main() {
+    let attr = quote!(name = "users", verbose);
+    let args = parse_meta!(attr => { name: LitStr, retries: i64 = 3, verbose?: bool }).unwrap();
+    assert_eq!(args.name.value(), "users");
+    assert_eq!(args.retries, 3);
+    assert_eq!(args.verbose, Some(true));
+
+    let attr = quote!(name = "users", retries = 5, verbose = false);
+    let args = parse_meta!(attr => { name: LitStr, retries: i64 = 3, verbose?: bool }).unwrap();
+    assert_eq!(args.retries, 5);
+    assert_eq!(args.verbose, Some(false));
+
+    let args = parse_meta!(quote!(name = "users") => { name: LitStr, verbose?: bool }).unwrap();
+    assert_eq!(args.name.value(), "users");
+    assert_eq!(args.verbose, None);
+
+    let err = parse_meta!(quote!(retries = 5) => { name: LitStr, retries: i64 }).err().unwrap();
+    assert_eq!(err.to_string(), "missing value for name");
+
+    let err = parse_meta!(quote!(name = "users", other = 1) => { name: LitStr }).err().unwrap();
+    assert_eq!(err.to_string(), "unknown key `other`");
+}